@@ -0,0 +1,61 @@
+use rltk::RGB;
+
+pub const FLOORS_PER_ACT: i32 = 4;
+pub const NUM_ACTS: i32 = 3;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Act {
+    Exordium,
+    City,
+    Beyond,
+}
+
+impl Act {
+    /// Returns the act that a floor belongs to
+    pub fn from_floor(floor: i32) -> Act {
+        match (floor - 1) / FLOORS_PER_ACT {
+            0 => Act::Exordium,
+            1 => Act::City,
+            _ => Act::Beyond,
+        }
+    }
+
+    pub fn to_name(self) -> String {
+        match self {
+            Act::Exordium => "Exordium".to_string(),
+            Act::City => "The City".to_string(),
+            Act::Beyond => "The Beyond".to_string(),
+        }
+    }
+
+    pub fn number(self) -> i32 {
+        match self {
+            Act::Exordium => 1,
+            Act::City => 2,
+            Act::Beyond => 3,
+        }
+    }
+
+    pub fn color(self) -> RGB {
+        match self {
+            Act::Exordium => RGB::named(rltk::GREEN),
+            Act::City => RGB::named(rltk::GOLD),
+            Act::Beyond => RGB::named(rltk::MEDIUM_PURPLE),
+        }
+    }
+}
+
+/// Returns the floor number within the floor's act, starting from 1
+pub fn floor_in_act(floor: i32) -> i32 {
+    (floor - 1) % FLOORS_PER_ACT + 1
+}
+
+/// The last floor of every act is a boss arena
+pub fn is_boss_floor(floor: i32) -> bool {
+    floor_in_act(floor) == FLOORS_PER_ACT
+}
+
+/// The boss of the final floor ends the run
+pub fn is_final_floor(floor: i32) -> bool {
+    floor == FLOORS_PER_ACT * NUM_ACTS
+}
\ No newline at end of file
@@ -4,16 +4,29 @@ use rltk::RGB;
 
 use super::super::{
     Name, Renderable, saveload,
-    effects, item, status
+    effects, item, status, stance::Stance
 };
 
+pub use super::super::item::CardType;
+
 pub enum Rarity {
     Common,
     Uncommon,
     Rare,
 }
 
-pub fn build_card<S: ToString>(ecs: &mut World, name: S, energy_cost: i32, rarity: Rarity) -> EntityBuilder {
+/// Cards from saves written before card types were stored count as Attacks if they deal damage
+pub fn card_type(ecs: &World, card: Entity) -> CardType {
+    if let Some(kind) = ecs.read_storage::<item::CardKind>().get(card) {
+        kind.card_type
+    } else if ecs.read_storage::<effects::DealDamage>().get(card).is_some() {
+        CardType::Attack
+    } else {
+        CardType::Skill
+    }
+}
+
+pub fn build_card<S: ToString>(ecs: &mut World, name: S, energy_cost: i32, rarity: Rarity, card_type: CardType) -> EntityBuilder {
     let color = match rarity {
         Rarity::Common => RGB::named(rltk::LIGHT_GRAY),
         Rarity::Uncommon => RGB::named(rltk::LIGHT_BLUE),
@@ -24,6 +37,7 @@ pub fn build_card<S: ToString>(ecs: &mut World, name: S, energy_cost: i32, rarit
         .with(Name{ name: name.to_string() })
         .with(item::Item{})
         .with(item::Card{ energy_cost })
+        .with(item::CardKind{ card_type })
         .with(Renderable{
             glyph: rltk::to_cp437('='),
             fg: color,
@@ -41,11 +55,15 @@ pub fn describe_card(ecs: &World, card: Entity) {
         let aoe = ecs.read_storage::<item::AreaOfEffect>();
         let fragile = ecs.read_storage::<item::Fragile>();
         let ethereal = ecs.read_storage::<item::Ethereal>();
+        let unplayable = ecs.read_storage::<item::Unplayable>();
+        if let Some(_) = unplayable.get(card) {
+            description.push("Unplayable.".to_string())
+        }
         if let Some(action) = targeted.get(card) {
             description.push(format!("Range {}.", action.range))
         }
         if let Some(action) = aoe.get(card) {
-            description.push(format!("AOE {}.", action.radius))
+            description.push(format!("{} {}.", action.shape.to_name(), action.radius))
         }
         if let Some(_) = fragile.get(card) {
             description.push("Fragile.".to_string())
@@ -116,11 +134,74 @@ pub fn describe_card(ecs: &World, card: Entity) {
         }
     }
 
+    {
+        let effect_lose_hp = ecs.read_storage::<effects::LoseHp>();
+        let effect_energy = ecs.read_storage::<effects::GainEnergy>();
+        let effect_heal = ecs.read_storage::<effects::HealAfterCombat>();
+        if let Some(action) = effect_lose_hp.get(card) {
+            description.push(format!("Lose {} HP.", action.amount));
+        }
+        if let Some(action) = effect_energy.get(card) {
+            description.push(format!("Gain {} energy.", action.amount));
+        }
+        if let Some(action) = effect_heal.get(card) {
+            description.push(format!("At the end of combat, heal {} HP.", action.amount));
+        }
+    }
+
+    {
+        let effect_channel = ecs.read_storage::<effects::ChannelOrb>();
+        let effect_evoke = ecs.read_storage::<effects::EvokeOrb>();
+        let effect_slots = ecs.read_storage::<effects::GainOrbSlots>();
+        if let Some(action) = effect_channel.get(card) {
+            description.push(format!("Channel {} {}.", action.number, action.orb.to_name()));
+        }
+        if let Some(action) = effect_evoke.get(card) {
+            description.push(format!("Evoke your next orb {} time(s).", action.number));
+        }
+        if let Some(action) = effect_slots.get(card) {
+            description.push(format!("Gain {} orb slot(s).", action.number));
+        }
+    }
+
+    {
+        let effect_stance = ecs.read_storage::<effects::ChangeStance>();
+        if let Some(action) = effect_stance.get(card) {
+            match action.stance {
+                Stance::Neutral => description.push("Exit your stance.".to_string()),
+                stance => description.push(format!("Enter {}.", stance.to_name())),
+            }
+        }
+    }
+
     {
         let effect_move = ecs.read_storage::<effects::Teleport>();
+        let effect_push = ecs.read_storage::<effects::Push>();
+        let effect_pull = ecs.read_storage::<effects::Pull>();
+        let effect_swap = ecs.read_storage::<effects::Swap>();
         if let Some(_) = effect_move.get(card) {
             description.push("Move to the targeted tile if possible.".to_string());
         }
+        if let Some(action) = effect_push.get(card) {
+            description.push(format!("Push {} tile(s), dealing {} damage on collision.", action.distance, action.collision_damage));
+        }
+        if let Some(action) = effect_pull.get(card) {
+            description.push(format!("Pull {} tile(s).", action.distance));
+        }
+        if let Some(_) = effect_swap.get(card) {
+            description.push("Swap places with the target.".to_string());
+        }
+    }
+
+    {
+        let effect_terrain = ecs.read_storage::<effects::CreateTerrain>();
+        if let Some(action) = effect_terrain.get(card) {
+            description.push(format!("Cover the area in {} for {} rounds.", action.terrain.to_name(), action.turns));
+        }
+        let effect_reveal = ecs.read_storage::<effects::RevealTraps>();
+        if let Some(action) = effect_reveal.get(card) {
+            description.push(format!("Reveal traps within {} tiles.", action.radius));
+        }
     }
 
     for desc in description {
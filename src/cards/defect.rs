@@ -0,0 +1,167 @@
+use specs::prelude::*;
+use rltk::RandomNumberGenerator;
+
+use super::super::{
+    Position, item, effects, status, orb::Orb
+};
+
+use super::card::{build_card, Rarity, CardType};
+
+fn strike(ecs: &mut World) -> Entity {
+    build_card(ecs, "Strike", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 6 })
+        .build()
+}
+
+fn defend(ecs: &mut World) -> Entity {
+    build_card(ecs, "Defend", 1, Rarity::Common, CardType::Skill)
+        .with(effects::GainBlock{ amount: 5 })
+        .build()
+}
+
+fn zap(ecs: &mut World) -> Entity {
+    build_card(ecs, "Zap", 1, Rarity::Common, CardType::Skill)
+        .with(effects::ChannelOrb{ orb: Orb::Lightning, number: 1 })
+        .build()
+}
+
+fn dualcast(ecs: &mut World) -> Entity {
+    build_card(ecs, "Dualcast", 1, Rarity::Common, CardType::Skill)
+        .with(effects::EvokeOrb{ number: 2 })
+        .build()
+}
+
+fn ball_lightning(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Ball Lightning", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 7 })
+        .with(effects::ChannelOrb{ orb: Orb::Lightning, number: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn beam_cell(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Beam Cell", 0, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 2 })
+        .with(item::Projectile{})
+        .with(effects::DealDamage{ amount: 3 })
+        .with(status::Vulnerable{ turns: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn sweeping_beam(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Sweeping Beam", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 3 })
+        .with(item::AreaOfEffect{ radius: 3, shape: item::AoeShape::Cone, friendly_fire: false })
+        .with(effects::DealDamage{ amount: 6 })
+        .with(effects::DrawCard{ number: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn cold_snap(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Cold Snap", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 6 })
+        .with(effects::ChannelOrb{ orb: Orb::Frost, number: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn coolheaded(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Coolheaded", 1, Rarity::Common, CardType::Skill)
+        .with(effects::ChannelOrb{ orb: Orb::Frost, number: 1 })
+        .with(effects::DrawCard{ number: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn leap(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Leap", 1, Rarity::Common, CardType::Skill)
+        .with(effects::GainBlock{ amount: 9 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn darkness(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Darkness", 1, Rarity::Uncommon, CardType::Skill)
+        .with(effects::ChannelOrb{ orb: Orb::Dark{ damage: 6 }, number: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn fusion(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Fusion", 2, Rarity::Uncommon, CardType::Skill)
+        .with(effects::ChannelOrb{ orb: Orb::Plasma, number: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn glacier(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Glacier", 2, Rarity::Uncommon, CardType::Skill)
+        .with(effects::GainBlock{ amount: 7 })
+        .with(effects::ChannelOrb{ orb: Orb::Frost, number: 2 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn capacitor(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Capacitor", 1, Rarity::Uncommon, CardType::Power)
+        .with(effects::GainOrbSlots{ number: 2 })
+        .with(item::Fragile{})
+        .with(Position{ x, y })
+        .build()
+}
+
+fn meteor_strike(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Meteor Strike", 5, Rarity::Rare, CardType::Attack)
+        .with(item::Targeted{ range: 2 })
+        .with(effects::DealDamage{ amount: 24 })
+        .with(effects::ChannelOrb{ orb: Orb::Plasma, number: 3 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn hyperbeam(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Hyperbeam", 2, Rarity::Rare, CardType::Attack)
+        .with(item::Targeted{ range: 6 })
+        .with(item::AreaOfEffect{ radius: 6, shape: item::AoeShape::Line, friendly_fire: false })
+        .with(effects::DealDamage{ amount: 18 })
+        .with(Position{ x, y })
+        .build()
+}
+
+pub fn random_card(ecs: &mut World, x: i32, y: i32) {
+    let roll: i32;
+    {
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        roll = rng.roll_dice(1, 12);
+    }
+
+    match roll {
+        1 => { ball_lightning(ecs, x, y); }
+        2 => { beam_cell(ecs, x, y); }
+        3 => { sweeping_beam(ecs, x, y); }
+        4 => { cold_snap(ecs, x, y); }
+        5 => { coolheaded(ecs, x, y); }
+        6 => { leap(ecs, x, y); }
+        7 => { darkness(ecs, x, y); }
+        8 => { fusion(ecs, x, y); }
+        9 => { glacier(ecs, x, y); }
+        10 => { capacitor(ecs, x, y); }
+        11 => { hyperbeam(ecs, x, y); }
+        _ => { meteor_strike(ecs, x, y); }
+    }
+}
+
+pub fn starter(ecs: &mut World) -> Vec<Entity> {
+    let mut init_deck = Vec::new();
+    for _ in 0 .. 4 { init_deck.push(strike(ecs)); }
+    for _ in 0 .. 4 { init_deck.push(defend(ecs)); }
+    init_deck.push(zap(ecs));
+    init_deck.push(dualcast(ecs));
+
+    init_deck
+}
\ No newline at end of file
@@ -0,0 +1,180 @@
+use specs::prelude::*;
+use rltk::RandomNumberGenerator;
+
+use super::super::{
+    Position, item, effects, status
+};
+
+use super::card::{build_card, Rarity, CardType};
+
+fn strike(ecs: &mut World) -> Entity {
+    build_card(ecs, "Strike", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 6 })
+        .build()
+}
+
+fn defend(ecs: &mut World) -> Entity {
+    build_card(ecs, "Defend", 1, Rarity::Common, CardType::Skill)
+        .with(effects::GainBlock{ amount: 5 })
+        .build()
+}
+
+fn bash(ecs: &mut World) -> Entity {
+    build_card(ecs, "Bash", 2, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 8 })
+        .with(effects::Push{ distance: 1, collision_damage: 4 })
+        .with(status::Vulnerable{ turns: 2 })
+        .build()
+}
+
+fn cleave(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Cleave", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 0 })
+        .with(item::AreaOfEffect{ radius: 1, shape: item::AoeShape::Circle, friendly_fire: false })
+        .with(effects::DealDamage{ amount: 8 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn clothesline(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Clothesline", 2, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 12 })
+        .with(status::Weak{ turns: 2 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn iron_wave(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Iron Wave", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 5 })
+        .with(effects::GainBlock{ amount: 5 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn pommel_strike(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Pommel Strike", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 9 })
+        .with(effects::DrawCard{ number: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn shrug_it_off(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Shrug It Off", 1, Rarity::Common, CardType::Skill)
+        .with(effects::GainBlock{ amount: 8 })
+        .with(effects::DrawCard{ number: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn thunderclap(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Thunderclap", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 0 })
+        .with(item::AreaOfEffect{ radius: 1, shape: item::AoeShape::Burst, friendly_fire: false })
+        .with(effects::DealDamage{ amount: 4 })
+        .with(status::Vulnerable{ turns: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn bloodletting(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Bloodletting", 0, Rarity::Uncommon, CardType::Skill)
+        .with(effects::LoseHp{ amount: 3 })
+        .with(effects::GainEnergy{ amount: 2 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn bloodthirst(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Bloodthirst", 1, Rarity::Uncommon, CardType::Skill)
+        .with(effects::HealAfterCombat{ amount: 8 })
+        .with(item::Fragile{})
+        .with(Position{ x, y })
+        .build()
+}
+
+fn hemokinesis(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Hemokinesis", 1, Rarity::Uncommon, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::LoseHp{ amount: 2 })
+        .with(effects::DealDamage{ amount: 15 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn inflame(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Inflame", 1, Rarity::Uncommon, CardType::Power)
+        .with(effects::BuffStrength{ amount: 2 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn shockwave(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Shockwave", 2, Rarity::Uncommon, CardType::Skill)
+        .with(item::Targeted{ range: 0 })
+        .with(item::AreaOfEffect{ radius: 2, shape: item::AoeShape::Ring, friendly_fire: false })
+        .with(status::Weak{ turns: 3 })
+        .with(status::Vulnerable{ turns: 3 })
+        .with(item::Fragile{})
+        .with(Position{ x, y })
+        .build()
+}
+
+fn uppercut(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Uppercut", 2, Rarity::Uncommon, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 13 })
+        .with(status::Weak{ turns: 1 })
+        .with(status::Vulnerable{ turns: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn offering(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Offering", 0, Rarity::Rare, CardType::Skill)
+        .with(effects::LoseHp{ amount: 6 })
+        .with(effects::GainEnergy{ amount: 2 })
+        .with(effects::DrawCard{ number: 3 })
+        .with(item::Fragile{})
+        .with(Position{ x, y })
+        .build()
+}
+
+pub fn random_card(ecs: &mut World, x: i32, y: i32) {
+    let roll: i32;
+    {
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        roll = rng.roll_dice(1, 13);
+    }
+
+    match roll {
+        1 => { cleave(ecs, x, y); }
+        2 => { clothesline(ecs, x, y); }
+        3 => { iron_wave(ecs, x, y); }
+        4 => { pommel_strike(ecs, x, y); }
+        5 => { shrug_it_off(ecs, x, y); }
+        6 => { thunderclap(ecs, x, y); }
+        7 => { bloodletting(ecs, x, y); }
+        8 => { bloodthirst(ecs, x, y); }
+        9 => { hemokinesis(ecs, x, y); }
+        10 => { inflame(ecs, x, y); }
+        11 => { shockwave(ecs, x, y); }
+        12 => { uppercut(ecs, x, y); }
+        _ => { offering(ecs, x, y); }
+    }
+}
+
+pub fn starter(ecs: &mut World) -> Vec<Entity> {
+    let mut init_deck = Vec::new();
+    for _ in 0 .. 5 { init_deck.push(strike(ecs)); }
+    for _ in 0 .. 4 { init_deck.push(defend(ecs)); }
+    init_deck.push(bash(ecs));
+
+    init_deck
+}
\ No newline at end of file
@@ -1,5 +1,8 @@
 mod card;
+pub mod defect;
+pub mod ironclad;
 pub mod neutral;
 pub mod silent;
+pub mod watcher;
 
-pub use card::describe_card;
\ No newline at end of file
+pub use card::{describe_card, card_type, CardType};
\ No newline at end of file
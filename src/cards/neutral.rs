@@ -4,10 +4,10 @@ use super::super::{
     Position, item, effects
 };
 
-use super::card::{build_card, Rarity};
+use super::card::{build_card, Rarity, CardType};
 
 pub fn shiv(ecs: &mut World) -> Entity {
-    build_card(ecs, "Shiv", 0, Rarity::Common)
+    build_card(ecs, "Shiv", 0, Rarity::Common, CardType::Attack)
         .with(item::Targeted{ range: 1 })
         .with(effects::DealDamage{ amount: 4 })
         .with(item::Ethereal{})
@@ -15,13 +15,26 @@ pub fn shiv(ecs: &mut World) -> Entity {
 }
 
 pub fn slimed(ecs: &mut World) -> Entity {
-    build_card(ecs, "Slimed", 1, Rarity::Common)
+    build_card(ecs, "Slimed", 1, Rarity::Common, CardType::Status)
         .with(item::Fragile{})
         .build()
 }
 
+pub fn dazed(ecs: &mut World) -> Entity {
+    build_card(ecs, "Dazed", 0, Rarity::Common, CardType::Status)
+        .with(item::Unplayable{})
+        .with(item::Ethereal{})
+        .build()
+}
+
+pub fn injury(ecs: &mut World) -> Entity {
+    build_card(ecs, "Injury", 0, Rarity::Common, CardType::Curse)
+        .with(item::Unplayable{})
+        .build()
+}
+
 fn finesse(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Finesse", 0, Rarity::Uncommon)
+    build_card(ecs, "Finesse", 0, Rarity::Uncommon, CardType::Skill)
         .with(effects::GainBlock{ amount: 2 })
         .with(effects::DrawCard{ number: 1 })
         .with(Position{ x, y })
@@ -29,7 +42,7 @@ fn finesse(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn flash_of_steel(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Flash of Steel", 0, Rarity::Uncommon)
+    build_card(ecs, "Flash of Steel", 0, Rarity::Uncommon, CardType::Attack)
         .with(item::Targeted{ range: 1 })
         .with(effects::DealDamage{ amount: 3 })
         .with(effects::DrawCard{ number: 1 })
@@ -2,26 +2,26 @@ use specs::prelude::*;
 use rltk::RandomNumberGenerator;
 
 use super::super::{
-    Position, item, effects, status
+    Position, item, effects, status, map::TileEffectType
 };
 
-use super::card::{build_card, Rarity};
+use super::card::{build_card, Rarity, CardType};
 
 fn strike(ecs: &mut World) -> Entity {
-    build_card(ecs, "Strike", 1, Rarity::Common)
+    build_card(ecs, "Strike", 1, Rarity::Common, CardType::Attack)
         .with(item::Targeted{ range: 1 })
         .with(effects::DealDamage{ amount: 6 })
         .build()
 }
 
 fn defend(ecs: &mut World) -> Entity {
-    build_card(ecs, "Defend", 1, Rarity::Common)
+    build_card(ecs, "Defend", 1, Rarity::Common, CardType::Skill)
         .with(effects::GainBlock{ amount: 5 })
         .build()
 }
 
 fn neutralize(ecs: &mut World) -> Entity {
-    build_card(ecs, "Neutralize", 0, Rarity::Common)
+    build_card(ecs, "Neutralize", 0, Rarity::Common, CardType::Attack)
         .with(item::Targeted{ range: 1 })
         .with(effects::DealDamage{ amount: 3 })
         .with(status::Weak{ turns: 1 })
@@ -29,14 +29,14 @@ fn neutralize(ecs: &mut World) -> Entity {
 }
 
 fn survivor(ecs: &mut World) -> Entity {
-    build_card(ecs, "Survivor", 1, Rarity::Common)
+    build_card(ecs, "Survivor", 1, Rarity::Common, CardType::Skill)
         .with(effects::GainBlock{ amount: 8 })
         .with(effects::DiscardCard{ number: 1 })
         .build()
 }
 
 fn acrobatics(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Acrobatics", 1, Rarity::Common)
+    build_card(ecs, "Acrobatics", 1, Rarity::Common, CardType::Skill)
         .with(item::Targeted{ range: 1 })
         .with(effects::Teleport{})
         .with(effects::DrawCard{ number: 3 })
@@ -46,7 +46,7 @@ fn acrobatics(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn backflip(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Backflip", 1, Rarity::Common)
+    build_card(ecs, "Backflip", 1, Rarity::Common, CardType::Skill)
         .with(item::Targeted{ range: 1 })
         .with(effects::Teleport{})
         .with(effects::GainBlock{ amount: 5 })
@@ -56,7 +56,7 @@ fn backflip(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn blade_dance(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Blade Dance", 1, Rarity::Common)
+    build_card(ecs, "Blade Dance", 1, Rarity::Common, CardType::Skill)
         .with(effects::GainCard{
             card: effects::GainableCard::Shiv,
             number: 2,
@@ -67,8 +67,9 @@ fn blade_dance(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn cloak_and_dagger(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Cloak And Dagger", 1, Rarity::Common)
+    build_card(ecs, "Cloak And Dagger", 1, Rarity::Common, CardType::Skill)
         .with(effects::GainBlock{ amount: 6 })
+        .with(effects::GainStealth{ amount: 2 })
         .with(effects::GainCard{
             card: effects::GainableCard::Shiv,
             number: 1,
@@ -79,8 +80,9 @@ fn cloak_and_dagger(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn dagger_throw(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Dagger Throw", 1, Rarity::Common)
+    build_card(ecs, "Dagger Throw", 1, Rarity::Common, CardType::Attack)
         .with(item::Targeted{ range: 2 })
+        .with(item::Projectile{})
         .with(effects::DealDamage{ amount: 5 })
         .with(effects::DrawCard{ number: 1 })
         .with(effects::DiscardCard{ number: 1 })
@@ -89,7 +91,7 @@ fn dagger_throw(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn deadly_poison(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Deadly Poison", 1, Rarity::Common)
+    build_card(ecs, "Deadly Poison", 1, Rarity::Common, CardType::Skill)
         .with(item::Targeted{ range: 2 })
         .with(status::Poison{ turns: 5 })
         .with(Position{ x, y })
@@ -97,14 +99,14 @@ fn deadly_poison(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn deflect(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Deflect", 0, Rarity::Common)
+    build_card(ecs, "Deflect", 0, Rarity::Common, CardType::Skill)
         .with(effects::GainBlock{ amount: 4 })
         .with(Position{ x, y })
         .build()
 }
 
 fn poisoned_stab(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Poisoned Stab", 1, Rarity::Common)
+    build_card(ecs, "Poisoned Stab", 1, Rarity::Common, CardType::Attack)
         .with(item::Targeted{ range: 1 })
         .with(effects::DealDamage{ amount: 6 })
         .with(status::Poison{ turns: 3 })
@@ -113,7 +115,7 @@ fn poisoned_stab(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn quick_slash(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Quick Slash", 1, Rarity::Common)
+    build_card(ecs, "Quick Slash", 1, Rarity::Common, CardType::Attack)
         .with(item::Targeted{ range: 1 })
         .with(effects::DealDamage{ amount: 8 })
         .with(effects::DrawCard{ number: 1 })
@@ -122,15 +124,24 @@ fn quick_slash(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn slice(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Slice", 0, Rarity::Common)
+    build_card(ecs, "Slice", 0, Rarity::Common, CardType::Attack)
         .with(item::Targeted{ range: 1 })
         .with(effects::DealDamage{ amount: 5 })
         .with(Position{ x, y })
         .build()
 }
 
+fn caltrops(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Caltrops", 1, Rarity::Uncommon, CardType::Skill)
+        .with(item::Targeted{ range: 2 })
+        .with(item::AreaOfEffect{ radius: 1, shape: item::AoeShape::Circle, friendly_fire: false })
+        .with(effects::CreateTerrain{ terrain: TileEffectType::Caltrops, turns: 8 })
+        .with(Position{ x, y })
+        .build()
+}
+
 fn dash(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Dash", 2, Rarity::Uncommon)
+    build_card(ecs, "Dash", 2, Rarity::Uncommon, CardType::Attack)
         .with(item::Targeted{ range: 1 })
         .with(effects::DealDamage{ amount: 10 })
         .with(effects::GainBlock{ amount: 10 })
@@ -139,14 +150,24 @@ fn dash(ecs: &mut World, x: i32, y: i32) -> Entity {
 }
 
 fn footwork(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Footwork", 1, Rarity::Uncommon)
+    build_card(ecs, "Footwork", 1, Rarity::Uncommon, CardType::Power)
         .with(effects::BuffDexterity{ amount: 4 })
+        .with(effects::RevealTraps{ radius: 4 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn tools_of_the_trade(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Tools of the Trade", 0, Rarity::Uncommon, CardType::Skill)
+        .with(effects::DrawCard{ number: 1 })
+        .with(effects::DiscardCard{ number: 1 })
+        .with(effects::RevealTraps{ radius: 8 })
         .with(Position{ x, y })
         .build()
 }
 
 fn leg_sweep(ecs: &mut World, x: i32, y: i32) -> Entity {
-    build_card(ecs, "Leg Sweep", 2, Rarity::Uncommon)
+    build_card(ecs, "Leg Sweep", 2, Rarity::Uncommon, CardType::Skill)
         .with(item::Targeted{ range: 1 })
         .with(effects::GainBlock{ amount: 10 })
         .with(status::Weak{ turns: 2 })
@@ -158,7 +179,7 @@ pub fn random_card(ecs: &mut World, x: i32, y: i32) {
     let roll: i32;
     {
         let mut rng = ecs.write_resource::<RandomNumberGenerator>();
-        roll = rng.roll_dice(1, 12);
+        roll = rng.roll_dice(1, 15);
     }
 
     match roll {
@@ -174,6 +195,8 @@ pub fn random_card(ecs: &mut World, x: i32, y: i32) {
         10 => { footwork(ecs, x, y); }
         11 => { deflect(ecs, x, y); }
         12 => { dagger_throw(ecs, x, y); }
+        13 => { caltrops(ecs, x, y); }
+        14 => { tools_of_the_trade(ecs, x, y); }
         _ => { leg_sweep(ecs, x, y); }
     }
 }
@@ -0,0 +1,143 @@
+use specs::prelude::*;
+use rltk::RandomNumberGenerator;
+
+use super::super::{
+    Position, item, effects, status, stance::Stance
+};
+
+use super::card::{build_card, Rarity, CardType};
+
+fn strike(ecs: &mut World) -> Entity {
+    build_card(ecs, "Strike", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 6 })
+        .build()
+}
+
+fn defend(ecs: &mut World) -> Entity {
+    build_card(ecs, "Defend", 1, Rarity::Common, CardType::Skill)
+        .with(effects::GainBlock{ amount: 5 })
+        .build()
+}
+
+fn eruption(ecs: &mut World) -> Entity {
+    build_card(ecs, "Eruption", 2, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 9 })
+        .with(effects::ChangeStance{ stance: Stance::Wrath })
+        .build()
+}
+
+fn vigilance(ecs: &mut World) -> Entity {
+    build_card(ecs, "Vigilance", 2, Rarity::Common, CardType::Skill)
+        .with(effects::GainBlock{ amount: 8 })
+        .with(effects::ChangeStance{ stance: Stance::Calm })
+        .build()
+}
+
+fn crescendo(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Crescendo", 1, Rarity::Common, CardType::Skill)
+        .with(effects::ChangeStance{ stance: Stance::Wrath })
+        .with(item::Fragile{})
+        .with(Position{ x, y })
+        .build()
+}
+
+fn tranquility(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Tranquility", 1, Rarity::Common, CardType::Skill)
+        .with(effects::ChangeStance{ stance: Stance::Calm })
+        .with(item::Fragile{})
+        .with(Position{ x, y })
+        .build()
+}
+
+fn empty_fist(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Empty Fist", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 9 })
+        .with(effects::ChangeStance{ stance: Stance::Neutral })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn empty_body(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Empty Body", 1, Rarity::Common, CardType::Skill)
+        .with(effects::GainBlock{ amount: 7 })
+        .with(effects::ChangeStance{ stance: Stance::Neutral })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn flurry_of_blows(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Flurry of Blows", 0, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 4 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn sash_whip(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Sash Whip", 1, Rarity::Common, CardType::Attack)
+        .with(item::Targeted{ range: 2 })
+        .with(effects::DealDamage{ amount: 8 })
+        .with(effects::Pull{ distance: 1 })
+        .with(status::Weak{ turns: 1 })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn tantrum(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Tantrum", 1, Rarity::Uncommon, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 9 })
+        .with(effects::ChangeStance{ stance: Stance::Wrath })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn fear_no_evil(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Fear No Evil", 1, Rarity::Uncommon, CardType::Attack)
+        .with(item::Targeted{ range: 1 })
+        .with(effects::DealDamage{ amount: 8 })
+        .with(effects::ChangeStance{ stance: Stance::Calm })
+        .with(Position{ x, y })
+        .build()
+}
+
+fn blasphemy(ecs: &mut World, x: i32, y: i32) -> Entity {
+    build_card(ecs, "Blasphemy", 1, Rarity::Rare, CardType::Skill)
+        .with(effects::ChangeStance{ stance: Stance::Divinity })
+        .with(item::Fragile{})
+        .with(Position{ x, y })
+        .build()
+}
+
+pub fn random_card(ecs: &mut World, x: i32, y: i32) {
+    let roll: i32;
+    {
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        roll = rng.roll_dice(1, 9);
+    }
+
+    match roll {
+        1 => { crescendo(ecs, x, y); }
+        2 => { tranquility(ecs, x, y); }
+        3 => { empty_fist(ecs, x, y); }
+        4 => { empty_body(ecs, x, y); }
+        5 => { flurry_of_blows(ecs, x, y); }
+        6 => { sash_whip(ecs, x, y); }
+        7 => { tantrum(ecs, x, y); }
+        8 => { fear_no_evil(ecs, x, y); }
+        _ => { blasphemy(ecs, x, y); }
+    }
+}
+
+pub fn starter(ecs: &mut World) -> Vec<Entity> {
+    let mut init_deck = Vec::new();
+    for _ in 0 .. 4 { init_deck.push(strike(ecs)); }
+    for _ in 0 .. 4 { init_deck.push(defend(ecs)); }
+    init_deck.push(eruption(ecs));
+    init_deck.push(vigilance(ecs));
+
+    init_deck
+}
\ No newline at end of file
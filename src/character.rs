@@ -0,0 +1,120 @@
+use specs::prelude::*;
+use serde::{Serialize, Deserialize};
+use rltk::RGB;
+
+use super::{cards, creature, relic::Relic};
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Character {
+    Ironclad,
+    Silent,
+    Defect,
+    Watcher,
+}
+
+pub const CHARACTERS: [Character; 4] = [Character::Ironclad, Character::Silent, Character::Defect, Character::Watcher];
+
+impl Character {
+    pub fn to_name(self) -> String {
+        match self {
+            Character::Ironclad => "Ironclad".to_string(),
+            Character::Silent => "Silent".to_string(),
+            Character::Defect => "Defect".to_string(),
+            Character::Watcher => "Watcher".to_string(),
+        }
+    }
+
+    pub fn glyph(self) -> rltk::FontCharType {
+        rltk::to_cp437('@')
+    }
+
+    pub fn color(self) -> RGB {
+        match self {
+            Character::Ironclad => RGB::from_f32(0.8, 0.1, 0.1),
+            Character::Silent => RGB::from_f32(0.1, 0.8, 0.1),
+            Character::Defect => RGB::from_f32(0.2, 0.5, 1.0),
+            Character::Watcher => RGB::from_f32(0.6, 0.3, 0.9),
+        }
+    }
+
+    pub fn max_hp(self) -> i32 {
+        match self {
+            Character::Ironclad => 80,
+            Character::Silent => 70,
+            Character::Defect => 75,
+            Character::Watcher => 72,
+        }
+    }
+
+    pub fn max_energy(self) -> i32 {
+        3
+    }
+
+    // Base stealth, which makes monsters slower to notice the player
+    pub fn stealth(self) -> i32 {
+        match self {
+            Character::Silent => 3,
+            _ => 0,
+        }
+    }
+
+    pub fn orb_slots(self) -> usize {
+        match self {
+            Character::Defect => 3,
+            _ => 0,
+        }
+    }
+
+    pub fn uses_stances(self) -> bool {
+        match self {
+            Character::Watcher => true,
+            _ => false,
+        }
+    }
+
+    pub fn starter_relic(self) -> Relic {
+        match self {
+            Character::Ironclad => Relic::BurningBlood,
+            Character::Silent => Relic::RingOfTheSnake,
+            Character::Defect => Relic::CrackedCore,
+            Character::Watcher => Relic::VioletLotus,
+        }
+    }
+
+    // Creates and returns the character's starting deck
+    pub fn starter_deck(self, ecs: &mut World) -> Vec<Entity> {
+        match self {
+            Character::Ironclad => cards::ironclad::starter(ecs),
+            Character::Silent => cards::silent::starter(ecs),
+            Character::Defect => cards::defect::starter(ecs),
+            Character::Watcher => cards::watcher::starter(ecs),
+        }
+    }
+
+    // Spawns a random card from the character's card pool
+    pub fn random_card(self, ecs: &mut World, x: i32, y: i32) {
+        match self {
+            Character::Ironclad => cards::ironclad::random_card(ecs, x, y),
+            Character::Silent => cards::silent::random_card(ecs, x, y),
+            Character::Defect => cards::defect::random_card(ecs, x, y),
+            Character::Watcher => cards::watcher::random_card(ecs, x, y),
+        }
+    }
+
+    pub fn next(self) -> Character {
+        let idx = CHARACTERS.iter().position(|c| *c == self).unwrap();
+        CHARACTERS[(idx + 1) % CHARACTERS.len()]
+    }
+
+    pub fn prev(self) -> Character {
+        let idx = CHARACTERS.iter().position(|c| *c == self).unwrap();
+        CHARACTERS[(idx + CHARACTERS.len() - 1) % CHARACTERS.len()]
+    }
+}
+
+/// Returns the character the player is playing as
+pub fn player_character(ecs: &World) -> Character {
+    let player_entity = ecs.fetch::<Entity>();
+    let players = ecs.read_storage::<creature::Player>();
+    players.get(*player_entity).unwrap().character
+}
\ No newline at end of file
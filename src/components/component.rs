@@ -23,4 +23,4 @@ pub struct Renderable {
     pub fg: RGB,
     pub bg: RGB,
     pub render_order: i32,
-}
+}
\ No newline at end of file
@@ -4,7 +4,7 @@ use specs::error::NoError;
 use specs_derive::{Component, ConvertSaveload};
 use serde::{Serialize, Deserialize};
 
-use super::super::{monsters};
+use super::super::{character::Character, monsters};
 
 use rltk::RandomNumberGenerator;
 
@@ -15,11 +15,166 @@ pub struct Creature {}
 pub struct Player {
     pub max_energy: i32,
     pub energy: i32,
+    pub character: Character,
 }
 
+// Marks the player while a monster is in view
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct InCombat {}
+
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct Monster {}
 
+// Marks the boss of an act, whose death opens the way to the next act
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Boss {}
+
+// Elites drop extra rewards when they die
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Elite {}
+
+// Elites fought together in one room of the floor, whose reward is only dropped by the last of them to die
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct EliteGroup {
+    pub room: usize
+}
+
+// Gremlins only lend their support to other gremlins
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Gremlin {}
+
+// Stationary monsters attack from where they stand instead of approaching
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Stationary {}
+
+// Gains Strength whenever the player plays a Skill in view
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct Enrage {
+    pub amount: i32
+}
+
+// Sleeping monsters do not act until they are damaged or the player
+// has been in view for the given number of turns
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct Asleep {
+    pub turns: i32
+}
+
+// Holds the gold stolen from the player, which is dropped on death
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct Thief {
+    pub stolen: i32
+}
+
+// Fleeing monsters run from the player instead of attacking; those that escape
+// leave the floor once they have been out of the player's sight for long enough
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct Fleeing {
+    pub unseen_turns: i32,
+    pub escapes: bool
+}
+
+// How a monster moves while hunting the player; monsters without one rush in
+#[derive(Component, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Behaviour {
+    // Closes in until the player is in range of its intent
+    Rusher,
+    // Keeps to the range of its intent, backing away when the player is adjacent
+    Kiter,
+    // Flees once its health drops below the given percentage
+    Skittish{ flee_percent: i32 },
+    // Holds its position, attacking only what comes in range, until it is damaged
+    Guard{ provoked: bool },
+}
+
+// How aware a monster is of the player
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Alertness {
+    Unaware,
+    Searching,
+    Hunting,
+}
+
+impl Alertness {
+    pub fn to_name(self) -> String {
+        match self {
+            Alertness::Unaware => "Unaware".to_string(),
+            Alertness::Searching => "Searching".to_string(),
+            Alertness::Hunting => "Hunting".to_string(),
+        }
+    }
+}
+
+// Monsters only chase and attack the player once they have noticed them, and search
+// where the player was last seen after losing sight of them
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Awareness {
+    pub state: Alertness,
+    pub last_seen: Option<rltk::Point>,
+    pub search_turns: i32,
+}
+
+// Turns a monster searches for the player before giving up
+pub const SEARCH_TURNS: i32 = 5;
+
+impl Awareness {
+    pub fn new() -> Awareness {
+        Awareness{ state: Alertness::Unaware, last_seen: None, search_turns: 0 }
+    }
+
+    pub fn notice(&mut self, player_pos: rltk::Point) {
+        self.state = Alertness::Hunting;
+        self.last_seen = Some(player_pos);
+    }
+
+    // Starts searching around the given point
+    pub fn investigate(&mut self, point: rltk::Point) {
+        self.state = Alertness::Searching;
+        self.last_seen = Some(point);
+        self.search_turns = SEARCH_TURNS;
+    }
+}
+
+// Percent chance for a monster that can see the player to notice them this turn
+pub fn detection_chance(distance: f32, lit: bool, stealth: i32, searching: bool) -> i32 {
+    let mut chance = 100 - (distance * 8.0) as i32 - stealth * 10;
+    if !lit { chance -= 30; }
+    if searching { chance += 20; }
+    i32::max(5, i32::min(100, chance))
+}
+
+// Makes the player harder to notice, with a bonus from cards that wears off each turn
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Stealth {
+    pub base: i32,
+    pub bonus: i32,
+}
+
+impl Stealth {
+    pub fn amount(&self) -> i32 {
+        self.base + self.bonus
+    }
+}
+
+// Splits into the given monsters at half health, which takes up its next turn
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Splits {
+    pub into: Vec<monsters::Mob>,
+    pub ready: bool,
+}
+
+// Switches to a defensive attack cycle after losing enough health in offensive mode,
+// and back again once the defensive cycle has played out
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct ModeShift {
+    pub offensive: Vec<monsters::Attacks>,
+    pub defensive: Vec<monsters::Attacks>,
+    pub threshold: i32,
+    pub damage_taken: i32,
+    pub block: i32,
+    pub defending: bool,
+}
+
 #[derive(Component, Debug, ConvertSaveload, Clone)]
 pub struct CombatStats {
     pub max_hp: i32,
@@ -41,6 +196,10 @@ pub struct Viewshed {
     pub dirty: bool,
 }
 
+// Marks a creature that changed tiles this turn, so the tile it entered can affect it
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct EntityMoved {}
+
 #[derive(Component, Debug, ConvertSaveload, Clone)]
 pub struct SufferDamage {
     pub amount: Vec<i32>
@@ -78,13 +237,25 @@ pub struct Intent {
     pub used: bool,
 }
 
-#[derive(Component, ConvertSaveload, Clone)]
+#[derive(Component, Serialize, Deserialize, Clone)]
 pub struct AttackCycle {
     pub attacks: Vec<monsters::Attacks>,
     pub cycle: usize,
 
     weights: Option<Vec<i32>>,
     total_weight: i32,
+
+    // The first attack is only ever used to open the fight
+    #[serde(default)]
+    opening: bool,
+    // The same attack is never used more than this many times in a row
+    #[serde(default)]
+    max_repeats: Option<usize>,
+    #[serde(default)]
+    repeats: usize,
+    // Attack cycle to switch to once health falls below the given percentage
+    #[serde(default)]
+    phase: Option<Box<(i32, AttackCycle)>>,
 }
 
 impl AttackCycle {
@@ -94,6 +265,10 @@ impl AttackCycle {
             cycle: 0,
             weights: None,
             total_weight: 0,
+            opening: false,
+            max_repeats: None,
+            repeats: 1,
+            phase: None,
         }
     }
 
@@ -103,6 +278,10 @@ impl AttackCycle {
             cycle: 0,
             weights: Some(Vec::new()),
             total_weight: 0,
+            opening: false,
+            max_repeats: None,
+            repeats: 1,
+            phase: None,
         }
     }
 
@@ -126,24 +305,181 @@ impl AttackCycle {
         self
     }
 
+    // Always opens with the given attack, which is never picked again
+    pub fn with_opening(mut self, attack: monsters::Attacks) -> AttackCycle {
+        self.attacks.insert(0, attack);
+        if let Some(w) = &mut self.weights {
+            w.insert(0, 0);
+        }
+        self.opening = true;
+        self.cycle = 0;
+
+        self
+    }
+
+    // Never uses the same attack more than the given number of times in a row
+    pub fn with_max_repeats(mut self, max_repeats: usize) -> AttackCycle {
+        self.max_repeats = Some(max_repeats);
+
+        self
+    }
+
+    // Switches to a different attack cycle once health falls below the given percentage
+    pub fn with_phase(mut self, hp_percent: i32, cycle: AttackCycle) -> AttackCycle {
+        self.phase = Some(Box::new((hp_percent, cycle)));
+
+        self
+    }
+
+    // Replaces the cycle with a new sequence of attacks, starting from the first
+    pub fn switch_sequential(&mut self, attacks: Vec<monsters::Attacks>) {
+        self.attacks = attacks;
+        self.cycle = 0;
+        self.weights = None;
+        self.total_weight = 0;
+        self.opening = false;
+        self.max_repeats = None;
+        self.repeats = 1;
+    }
+
+    // Switches to the next phase if health has fallen far enough, returning whether it did
+    pub fn enter_phase(&mut self, hp: i32, max_hp: i32) -> bool {
+        let below_threshold = match &self.phase {
+            Some(phase) => hp * 100 < phase.0 * max_hp,
+            None => false,
+        };
+        if below_threshold {
+            let (_, next) = *self.phase.take().unwrap();
+            *self = next;
+        }
+        below_threshold
+    }
+
+    // Whether the current attack has been used as many times in a row as allowed
+    fn repeat_limited(&self) -> bool {
+        match self.max_repeats {
+            Some(max) => self.repeats >= max,
+            None => false,
+        }
+    }
+
+    // Picks a weighted attack, skipping any that match the excluded attack
+    fn roll_weighted(&self, rng: &mut RandomNumberGenerator, weights: &[i32], exclude: Option<&monsters::Attacks>) -> usize {
+        let allowed: Vec<(usize, i32)> = weights.iter().cloned().enumerate()
+            .filter(|(i, _)| exclude.map_or(true, |e| self.attacks[*i] != *e))
+            .collect();
+        let total: i32 = allowed.iter().map(|(_, w)| w).sum();
+        if total < 1 { return self.cycle; }
+
+        let mut roll = rng.roll_dice(1, total) - 1;
+        for (i, w) in allowed {
+            if roll < w { return i; }
+            roll -= w;
+        }
+        self.cycle
+    }
+
     pub fn next_attack(&mut self, rng: &mut RandomNumberGenerator) {
-        match &self.weights {
+        let previous = self.attacks[self.cycle].clone();
+        let exclude = if self.repeat_limited() { Some(&previous) } else { None };
+
+        let next_cycle = match &self.weights {
             Some(w) => {
-                let mut roll = rng.roll_dice(1, self.total_weight) - 1;
-                let mut next_cycle = 0;
-
-                while roll >= 0 {
-                    if roll < w[next_cycle] {
-                        self.cycle = next_cycle;
-                        return;
-                    }
-                    roll -= w[next_cycle];
-                    next_cycle += 1;
+                let mut next = self.roll_weighted(rng, w, exclude);
+                // Fall back on repeating the attack if nothing else can be picked
+                if self.attacks[next] == previous && exclude.is_some() {
+                    next = self.roll_weighted(rng, w, None);
                 }
+                next
             }
             None => {
-                self.cycle = (self.cycle + 1) % self.attacks.len();
+                let start = if self.opening { 1 } else { 0 };
+                let mut next = self.cycle + 1;
+                if next >= self.attacks.len() { next = usize::min(start, self.attacks.len() - 1); }
+                next
             }
+        };
+
+        if self.attacks[next_cycle] == previous {
+            self.repeats += 1;
+        } else {
+            self.repeats = 1;
         }
+        self.cycle = next_cycle;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use monsters::Attacks;
+
+    fn attack(name: &str) -> Attacks {
+        Attacks::NormalAttack{ name: name.to_string(), range: 1, amount: 1 }
+    }
+
+    fn sample(mut ac: AttackCycle, turns: usize) -> Vec<String> {
+        let mut rng = RandomNumberGenerator::seeded(42);
+        let mut used = Vec::new();
+        for _ in 0 .. turns {
+            if let Attacks::NormalAttack{name, ..} = &ac.attacks[ac.cycle] {
+                used.push(name.clone());
+            }
+            ac.next_attack(&mut rng);
+        }
+        used
+    }
+
+    #[test]
+    fn opening_attack_is_used_once() {
+        let ac = AttackCycle::new_weighted()
+            .add_weighted(attack("A"), 1)
+            .add_weighted(attack("B"), 1)
+            .with_opening(attack("Open"));
+        let used = sample(ac, 200);
+        assert_eq!(used[0], "Open");
+        assert!(used[1..].iter().all(|name| name != "Open"));
+    }
+
+    #[test]
+    fn sequential_opening_is_not_repeated() {
+        let ac = AttackCycle::new_sequential()
+            .add_sequential(attack("A"))
+            .add_sequential(attack("B"))
+            .with_opening(attack("Open"));
+        assert_eq!(sample(ac, 5), vec!["Open", "A", "B", "A", "B"]);
+    }
+
+    #[test]
+    fn weighted_attacks_follow_their_weights() {
+        let ac = AttackCycle::new_weighted()
+            .add_weighted(attack("A"), 1)
+            .add_weighted(attack("B"), 3);
+        let used = sample(ac, 4000);
+        let b_count = used.iter().filter(|name| *name == "B").count();
+        assert!(b_count > 2800 && b_count < 3200, "B was used {} times", b_count);
+    }
+
+    #[test]
+    fn max_repeats_limits_streaks() {
+        let ac = AttackCycle::new_weighted()
+            .add_weighted(attack("A"), 9)
+            .add_weighted(attack("B"), 1)
+            .with_max_repeats(2);
+        let used = sample(ac, 2000);
+        assert!(used.windows(3).all(|w| !(w[0] == w[1] && w[1] == w[2])));
+        assert!(used.iter().any(|name| name == "B"));
+    }
+
+    #[test]
+    fn phase_switches_below_threshold() {
+        let mut ac = AttackCycle::new_sequential()
+            .add_sequential(attack("A"))
+            .with_phase(50, AttackCycle::new_sequential().add_sequential(attack("Enraged")));
+        assert!(!ac.enter_phase(60, 100));
+        assert!(!ac.enter_phase(50, 100));
+        assert!(ac.enter_phase(49, 100));
+        assert_eq!(sample(ac.clone(), 2), vec!["Enraged", "Enraged"]);
+        assert!(!ac.enter_phase(10, 100));
     }
 }
\ No newline at end of file
@@ -4,7 +4,10 @@ use specs::error::NoError;
 use specs_derive::{Component, ConvertSaveload};
 use serde::{Serialize, Deserialize};
 
-use super::super::cards;
+use super::super::{cards, map::TileEffectType};
+use super::orb::Orb;
+use super::passive::Passive;
+use super::stance::Stance;
 
 #[derive(Component, Debug, ConvertSaveload, Clone)]
 pub struct DealDamage {
@@ -30,13 +33,15 @@ pub struct DrawCard {
 pub enum GainableCard {
     Shiv,
     Slimed,
+    Dazed,
 }
 
 impl GainableCard {
     pub fn to_name(self) -> String {
         match self {
             GainableCard::Shiv => "Shiv".to_string(),
-            GainableCard::Slimed => "Slimed".to_string()
+            GainableCard::Slimed => "Slimed".to_string(),
+            GainableCard::Dazed => "Dazed".to_string(),
         }
     }
 
@@ -45,6 +50,7 @@ impl GainableCard {
         match self {
             GainableCard::Shiv => { cards::neutral::shiv(ecs) }
             GainableCard::Slimed => { cards::neutral::slimed(ecs) }
+            GainableCard::Dazed => { cards::neutral::dazed(ecs) }
         }
     }
 }
@@ -66,5 +72,119 @@ pub struct BuffDexterity {
     pub amount: i32
 }
 
+// Reduces the Strength of affected targets
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct DebuffStrength {
+    pub amount: i32
+}
+
+// Reduces the Dexterity of affected targets
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct DebuffDexterity {
+    pub amount: i32
+}
+
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
-pub struct Teleport {}
\ No newline at end of file
+pub struct Teleport {}
+
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct LoseHp {
+    pub amount: i32
+}
+
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct GainEnergy {
+    pub amount: i32
+}
+
+// Makes the caster harder to notice for a few turns
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct GainStealth {
+    pub amount: i32
+}
+
+// Gives block to affected targets
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct GrantBlock {
+    pub amount: i32
+}
+
+// Gives strength to affected targets
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct GrantStrength {
+    pub amount: i32
+}
+
+// Takes gold from the player and holds on to it until the caster dies or escapes
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct StealGold {
+    pub amount: i32
+}
+
+// The caster stops fighting and runs from the player
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Flee {}
+
+// Knocks targets away from the caster, dealing extra damage to any that slam into a wall or creature
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct Push {
+    pub distance: i32,
+    pub collision_damage: i32,
+}
+
+// Drags targets towards the caster until something is in the way
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct Pull {
+    pub distance: i32
+}
+
+// Covers the affected tiles, or the caster's tile when untargeted, in terrain for a number of rounds
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct CreateTerrain {
+    pub terrain: TileEffectType,
+    pub turns: i32,
+}
+
+// Reveals hidden traps the caster can see within the radius
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct RevealTraps {
+    pub radius: i32,
+}
+
+// The caster and its target exchange places
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Swap {}
+
+// Heals the caster when combat ends, stacking on the caster until then
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct HealAfterCombat {
+    pub amount: i32
+}
+
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct ChannelOrb {
+    pub orb: Orb,
+    pub number: i32,
+}
+
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct EvokeOrb {
+    pub number: i32
+}
+
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct GainOrbSlots {
+    pub number: i32
+}
+
+// Grants the caster a passive, stacking with any copy it already has
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct GainPassive {
+    pub passive: Passive
+}
+
+// Changing to Stance::Neutral exits the caster's current stance
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct ChangeStance {
+    pub stance: Stance
+}
\ No newline at end of file
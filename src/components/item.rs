@@ -3,6 +3,9 @@ use specs::saveload::{Marker, ConvertSaveload};
 use specs::error::NoError;
 use specs_derive::{Component, ConvertSaveload};
 use serde::{Serialize, Deserialize};
+use rltk::Point;
+
+use super::super::map::Map;
 
 // Items can be picked up off the ground
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
@@ -17,12 +20,37 @@ pub struct InBackpack {
     pub owner: Entity
 }
 
+// Gold piles are added to the player's purse when picked up
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct GoldPile {
+    pub amount: i32
+}
+
+// Keys are kept for the floor they were found on and open one locked door each
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Key {}
+
 // Cards are added to the deck when acquired and can be played
 #[derive(Component, Debug, ConvertSaveload, Clone)]
 pub struct Card {
     pub energy_cost: i32
 }
 
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum CardType {
+    Attack,
+    Skill,
+    Power,
+    Status,
+    Curse,
+}
+
+// Whether a card is an Attack, Skill, Power, Status or Curse
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct CardKind {
+    pub card_type: CardType
+}
+
 // Ethereal cards are destroyed when discarded
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct Ethereal{}
@@ -31,12 +59,102 @@ pub struct Ethereal{}
 #[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct Fragile{}
 
+// Unplayable cards only take up space in the hand
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Unplayable{}
+
 #[derive(Component, Debug, ConvertSaveload, Clone)]
 pub struct Targeted {
     pub range: i32
 }
 
-#[derive(Component, Debug, ConvertSaveload, Clone)]
+// Targeted actions that aim at the caster's allies instead of its enemies
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct TargetsAllies {}
+
+// Targeted actions that hit the first creature in their line of fire
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Projectile {}
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum AoeShape {
+    // Every tile within the radius of the target
+    Circle,
+    // Tiles from the caster through the target, out to the radius, passing through creatures
+    Line,
+    // A wedge out to the radius, spreading from the caster towards the target
+    Cone,
+    // Tiles at the radius from the target, leaving its centre untouched
+    Ring,
+    // Every tile within the radius of the caster, wherever it aims
+    Burst,
+}
+
+impl AoeShape {
+    pub fn to_name(self) -> String {
+        match self {
+            AoeShape::Circle => "AOE".to_string(),
+            AoeShape::Line => "Line".to_string(),
+            AoeShape::Cone => "Cone".to_string(),
+            AoeShape::Ring => "Ring".to_string(),
+            AoeShape::Burst => "Burst".to_string(),
+        }
+    }
+}
+
+impl Default for AoeShape {
+    fn default() -> Self { AoeShape::Circle }
+}
+
+// Affects the tiles in its shape instead of a single target. Without friendly fire
+// only the caster's enemies are hit; with it the caster and its allies are hit too
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
 pub struct AreaOfEffect {
-    pub radius: i32
+    pub radius: i32,
+    #[serde(default)]
+    pub shape: AoeShape,
+    #[serde(default)]
+    pub friendly_fire: bool,
+}
+
+// Half-angle of a cone, as the cosine between its centre and its edge
+const CONE_SPREAD: f32 = 0.7;
+
+impl AreaOfEffect {
+    /// Tiles affected when the caster at `origin` aims at `target`
+    pub fn tiles(&self, map: &Map, origin: Point, target: Point) -> Vec<Point> {
+        let mut tiles = match self.shape {
+            AoeShape::Circle => rltk::field_of_view(target, self.radius, map),
+            AoeShape::Ring => {
+                let mut tiles = rltk::field_of_view(target, self.radius, map);
+                tiles.retain(|p| rltk::DistanceAlg::Pythagoras.distance2d(target, *p).round() as i32 == self.radius);
+                tiles
+            }
+            AoeShape::Burst => rltk::field_of_view(origin, self.radius, map),
+            AoeShape::Line => {
+                let (dx, dy) = (target.x - origin.x, target.y - origin.y);
+                let steps = i32::max(dx.abs(), dy.abs());
+                if steps == 0 { return Vec::new(); }
+                let end = Point::new(
+                    origin.x + (dx as f32 * self.radius as f32 / steps as f32).round() as i32,
+                    origin.y + (dy as f32 * self.radius as f32 / steps as f32).round() as i32,
+                );
+                map.line_of_fire(origin, end, false)
+            }
+            AoeShape::Cone => {
+                let (dx, dy) = ((target.x - origin.x) as f32, (target.y - origin.y) as f32);
+                let length = (dx * dx + dy * dy).sqrt();
+                if length == 0.0 { return Vec::new(); }
+                let mut tiles = rltk::field_of_view(origin, self.radius, map);
+                tiles.retain(|p| {
+                    let (px, py) = ((p.x - origin.x) as f32, (p.y - origin.y) as f32);
+                    let distance = (px * px + py * py).sqrt();
+                    distance > 0.0 && (px * dx + py * dy) / (distance * length) >= CONE_SPREAD
+                });
+                tiles
+            }
+        };
+        tiles.retain(|p| p.x > 0 && p.x < map.width - 1 && p.y > 0 && p.y < map.height - 1);
+        tiles
+    }
 }
\ No newline at end of file
@@ -2,4 +2,8 @@ pub mod component;
 pub mod creature;
 pub mod effects;
 pub mod item;
+pub mod orb;
+pub mod passive;
+pub mod relic;
+pub mod stance;
 pub mod status;
\ No newline at end of file
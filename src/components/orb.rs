@@ -0,0 +1,106 @@
+use specs::prelude::*;
+use specs_derive::Component;
+use serde::{Serialize, Deserialize};
+
+use rltk::RGB;
+
+pub const MAX_ORB_SLOTS: usize = 10;
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Orb {
+    Lightning,
+    Frost,
+    Dark{ damage: i32 },
+    Plasma,
+}
+
+impl Orb {
+    pub fn to_name(self) -> String {
+        match self {
+            Orb::Lightning => "Lightning".to_string(),
+            Orb::Frost => "Frost".to_string(),
+            Orb::Dark{..} => "Dark".to_string(),
+            Orb::Plasma => "Plasma".to_string(),
+        }
+    }
+
+    pub fn glyph(self) -> char {
+        match self {
+            Orb::Lightning => 'L',
+            Orb::Frost => 'F',
+            Orb::Dark{..} => 'D',
+            Orb::Plasma => 'P',
+        }
+    }
+
+    pub fn color(self) -> RGB {
+        match self {
+            Orb::Lightning => RGB::named(rltk::YELLOW),
+            Orb::Frost => RGB::named(rltk::CYAN),
+            Orb::Dark{..} => RGB::named(rltk::PURPLE),
+            Orb::Plasma => RGB::named(rltk::ORANGE),
+        }
+    }
+
+    // Damage dealt to a monster in view when the orb triggers
+    pub fn damage(self, evoke: bool) -> i32 {
+        match self {
+            Orb::Lightning => if evoke { 8 } else { 3 },
+            Orb::Dark{damage} => if evoke { damage } else { 0 },
+            _ => 0,
+        }
+    }
+
+    // Block gained by the player when the orb triggers
+    pub fn block(self, evoke: bool) -> i32 {
+        match self {
+            Orb::Frost => if evoke { 5 } else { 2 },
+            _ => 0,
+        }
+    }
+
+    // Energy gained by the player when the orb triggers
+    pub fn energy(self, evoke: bool) -> i32 {
+        match self {
+            Orb::Plasma => if evoke { 2 } else { 1 },
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct OrbSlots {
+    pub max_slots: usize,
+    pub orbs: Vec<Orb>,
+    // Orbs that have left their slots and are waiting to be evoked
+    pub evoked: Vec<Orb>,
+}
+
+impl OrbSlots {
+    pub fn new(max_slots: usize) -> OrbSlots {
+        OrbSlots{ max_slots, orbs: Vec::new(), evoked: Vec::new() }
+    }
+
+    /// Channels an orb into the rightmost slot, evoking the leftmost orb if all slots are full
+    pub fn channel(&mut self, orb: Orb) {
+        if self.max_slots == 0 { return; }
+        if self.orbs.len() >= self.max_slots {
+            let oldest = self.orbs.remove(0);
+            self.evoked.push(oldest);
+        }
+        self.orbs.push(orb);
+    }
+
+    /// Evokes the leftmost orb the given number of times
+    pub fn evoke(&mut self, times: i32) {
+        if self.orbs.is_empty() { return; }
+        let orb = self.orbs.remove(0);
+        for _ in 0 .. times {
+            self.evoked.push(orb);
+        }
+    }
+
+    pub fn add_slots(&mut self, number: usize) {
+        self.max_slots = usize::min(MAX_ORB_SLOTS, self.max_slots + number);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use specs::prelude::*;
+use specs_derive::Component;
+use serde::{Serialize, Deserialize};
+
+// The moments in a turn at which passives take effect
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Trigger {
+    TurnStart,
+    Damaged,
+    Death,
+}
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Passive {
+    // Gain Strength at the start of every turn
+    Ritual{ amount: i32 },
+    // Gain Block the first time it is damaged
+    CurlUp{ amount: i32 },
+    // Gain Strength whenever it is damaged
+    Angry{ amount: i32 },
+    // Apply Vulnerable to nearby creatures on death
+    SporeCloud{ turns: i32, radius: i32 },
+}
+
+impl Passive {
+    pub fn to_name(self) -> String {
+        match self {
+            Passive::Ritual{amount} => format!("Ritual {}", amount),
+            Passive::CurlUp{amount} => format!("Curl Up {}", amount),
+            Passive::Angry{amount} => format!("Angry {}", amount),
+            Passive::SporeCloud{turns, radius: _} => format!("Spore Cloud {}", turns),
+        }
+    }
+
+    pub fn trigger(self) -> Trigger {
+        match self {
+            Passive::Ritual{..} => Trigger::TurnStart,
+            Passive::CurlUp{..} | Passive::Angry{..} => Trigger::Damaged,
+            Passive::SporeCloud{..} => Trigger::Death,
+        }
+    }
+
+    // Passives that are lost once they have taken effect
+    pub fn once(self) -> bool {
+        matches!(self, Passive::CurlUp{..})
+    }
+
+    // Stacks another copy of the same passive into this one, returning false if they differ
+    pub fn stack(&mut self, other: Passive) -> bool {
+        match (self, other) {
+            (Passive::Ritual{amount}, Passive::Ritual{amount: more})
+            | (Passive::CurlUp{amount}, Passive::CurlUp{amount: more})
+            | (Passive::Angry{amount}, Passive::Angry{amount: more}) => {
+                *amount += more;
+                true
+            }
+            (Passive::SporeCloud{turns, ..}, Passive::SporeCloud{turns: more, ..}) => {
+                *turns += more;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Passives {
+    pub passives: Vec<Passive>
+}
+
+impl Passives {
+    pub fn new(passives: Vec<Passive>) -> Passives {
+        Passives{ passives }
+    }
+
+    // Passives that take effect on the given trigger
+    pub fn triggered(&self, trigger: Trigger) -> Vec<Passive> {
+        self.passives.iter().copied().filter(|p| p.trigger() == trigger).collect()
+    }
+
+    // Adds a passive, stacking it onto an existing copy of the same passive
+    pub fn add(&mut self, passive: Passive) {
+        for existing in self.passives.iter_mut() {
+            if existing.stack(passive) { return; }
+        }
+        self.passives.push(passive);
+    }
+
+    // Removes passives that only take effect once after they have been triggered
+    pub fn expire(&mut self, trigger: Trigger) {
+        self.passives.retain(|p| !(p.trigger() == trigger && p.once()));
+    }
+}
\ No newline at end of file
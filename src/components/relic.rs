@@ -0,0 +1,36 @@
+use specs::prelude::*;
+use specs_derive::Component;
+use serde::{Serialize, Deserialize};
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Relic {
+    BurningBlood,
+    RingOfTheSnake,
+    CrackedCore,
+    VioletLotus,
+}
+
+impl Relic {
+    pub fn to_name(self) -> String {
+        match self {
+            Relic::BurningBlood => "Burning Blood".to_string(),
+            Relic::RingOfTheSnake => "Ring of the Snake".to_string(),
+            Relic::CrackedCore => "Cracked Core".to_string(),
+            Relic::VioletLotus => "Violet Lotus".to_string(),
+        }
+    }
+
+    pub fn description(self) -> String {
+        match self {
+            Relic::BurningBlood => "At the end of combat, heal 6 HP.".to_string(),
+            Relic::RingOfTheSnake => "At the start of combat, draw 2 additional cards.".to_string(),
+            Relic::CrackedCore => "At the start of combat, channel 1 Lightning.".to_string(),
+            Relic::VioletLotus => "Whenever you exit Calm, gain an additional 1 energy.".to_string(),
+        }
+    }
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct Relics {
+    pub relics: Vec<Relic>
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+use specs::prelude::*;
+use specs_derive::Component;
+use serde::{Serialize, Deserialize};
+use rltk::RGB;
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Stance {
+    Neutral,
+    Wrath,
+    Calm,
+    Divinity,
+}
+
+impl Stance {
+    pub fn to_name(self) -> String {
+        match self {
+            Stance::Neutral => "Neutral".to_string(),
+            Stance::Wrath => "Wrath".to_string(),
+            Stance::Calm => "Calm".to_string(),
+            Stance::Divinity => "Divinity".to_string(),
+        }
+    }
+
+    pub fn color(self) -> RGB {
+        match self {
+            Stance::Neutral => RGB::named(rltk::GREY),
+            Stance::Wrath => RGB::named(rltk::RED),
+            Stance::Calm => RGB::named(rltk::LIGHT_BLUE),
+            Stance::Divinity => RGB::named(rltk::GOLD),
+        }
+    }
+
+    // Multiplier on damage dealt while in the stance
+    pub fn damage_dealt(self) -> f32 {
+        match self {
+            Stance::Wrath => 2.0,
+            Stance::Divinity => 3.0,
+            _ => 1.0,
+        }
+    }
+
+    // Multiplier on damage taken while in the stance
+    pub fn damage_taken(self) -> f32 {
+        match self {
+            Stance::Wrath => 2.0,
+            _ => 1.0,
+        }
+    }
+
+    // Energy gained when entering the stance
+    pub fn enter_energy(self) -> i32 {
+        match self {
+            Stance::Divinity => 3,
+            _ => 0,
+        }
+    }
+
+    // Energy gained when exiting the stance
+    pub fn exit_energy(self) -> i32 {
+        match self {
+            Stance::Calm => 2,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone)]
+pub struct InStance {
+    pub stance: Stance
+}
\ No newline at end of file
@@ -22,4 +22,41 @@ pub struct Frail {
 #[derive(Component, Debug, ConvertSaveload, Clone)]
 pub struct Poison {
     pub turns: i32
+}
+
+// Entangled creatures cannot move, but can still act
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct Entangled {
+    pub turns: i32
+}
+
+// Rooted creatures cannot teleport
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct Rooted {
+    pub turns: i32
+}
+
+// Slowed creatures take two turns to move, getting started on the first and moving on the second
+#[derive(Component, Debug, ConvertSaveload, Clone)]
+pub struct Slowed {
+    pub turns: i32,
+    pub started: bool
+}
+
+// Returns the status stopping a creature from moving this turn, if any
+pub fn movement_blocked(entangled: Option<&Entangled>, slowed: Option<&mut Slowed>) -> Option<&'static str> {
+    if entangled.is_some() {
+        return Some("Entangled");
+    }
+    match slowed {
+        Some(s) if !s.started => {
+            s.started = true;
+            Some("Slowed")
+        }
+        Some(s) => {
+            s.started = false;
+            None
+        }
+        None => None,
+    }
 }
\ No newline at end of file
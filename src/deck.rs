@@ -1,11 +1,12 @@
 use specs::prelude::*;
 
 use rand::thread_rng;
+use rand::Rng;
 use rand::seq::SliceRandom;
 
 pub const MAX_HAND_SIZE: usize = 10;
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Deck {
     pub hand: Vec<Entity>,
     pub draw: Vec<Entity>,
@@ -22,6 +23,12 @@ impl Deck {
         self.draw_card();
     }
 
+    /// Shuffles a card into a random place in the draw pile
+    pub fn shuffle_in(&mut self, c: Entity) {
+        let i = thread_rng().gen_range(0, self.draw.len() + 1);
+        self.draw.insert(i, c);
+    }
+
     pub fn gain_multiple_cards(&mut self, cards: Vec<Entity>) {
         for c in cards {
             self.gain_card(c)
@@ -0,0 +1,257 @@
+use rltk::Point;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::map::{Map, TileType};
+
+// How strongly the flee map prefers getting far from the player over reaching a dead end quickly
+const FLEE_WEIGHT: f32 = 1.2;
+
+const NEIGHBOURS: [(i32, i32); 8] = [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+/// Pathing distances to the player, built once per monster turn and shared by every monster.
+/// Monsters approach by stepping down `approach` and run away by stepping down `flee`.
+#[derive(Default)]
+pub struct FlowField {
+    pub approach: Vec<i32>,
+    pub flee: Vec<i32>,
+    target: usize,
+    enter_target: bool,
+}
+
+impl FlowField {
+    /// Builds both maps around the given point, treating only terrain as obstacles so
+    /// that monsters in the way are walked around rather than changing the distances
+    pub fn new(map: &Map, target: Point) -> FlowField {
+        let target = map.xy_idx(target.x, target.y);
+        let approach = dijkstra(map, vec![(target, 0)]);
+
+        // Seeding every tile with a scaled negative distance makes open areas far from
+        // the player attractive, so fleeing monsters avoid running into corners
+        let seeds = approach.iter().enumerate()
+            .filter(|(_, cost)| **cost != i32::MAX)
+            .map(|(idx, cost)| (idx, -(*cost as f32 * FLEE_WEIGHT) as i32))
+            .collect();
+        let flee = dijkstra(map, seeds);
+
+        FlowField{ approach, flee, target, enter_target: false }
+    }
+
+    /// Builds only the approach map around an empty spot, such as where the player was last seen,
+    /// which monsters are allowed to step onto
+    pub fn toward(map: &Map, target: Point) -> FlowField {
+        let target = map.xy_idx(target.x, target.y);
+        let approach = dijkstra(map, vec![(target, 0)]);
+        FlowField{ approach, flee: Vec::new(), target, enter_target: true }
+    }
+
+    /// Open neighbour that brings a monster closest to the player, which may be a door to open
+    pub fn step_toward(&self, map: &Map, idx: usize) -> Option<usize> {
+        self.descend(map, &self.approach, idx, true)
+    }
+
+    /// Open neighbour that best gets a monster away from the player
+    pub fn step_away(&self, map: &Map, idx: usize) -> Option<usize> {
+        self.descend(map, &self.flee, idx, false)
+    }
+
+    // Picks the lowest unblocked neighbour, as long as it is an improvement on the current tile
+    fn descend(&self, map: &Map, field: &[i32], idx: usize, open_doors: bool) -> Option<usize> {
+        let x = idx as i32 % map.width;
+        let y = idx as i32 / map.width;
+        let mut best: Option<usize> = None;
+        for (dx, dy) in NEIGHBOURS.iter() {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx < 0 || nx >= map.width || ny < 0 || ny >= map.height { continue; }
+            let next = map.xy_idx(nx, ny);
+            let door = open_doors && map.tiles[next] == TileType::ClosedDoor;
+            if (map.blocked[next] && !door) || (next == self.target && !self.enter_target) { continue; }
+            if field[next] >= field[idx] { continue; }
+            if best.map_or(true, |b| field[next] < field[b]) {
+                best = Some(next);
+            }
+        }
+        best
+    }
+}
+
+// Spreads out from the seeds around walls, deep water and locked doors, returning the cost in hundredths of a tile
+// to reach every tile, with slow, dangerous or trapped tiles costing more to step onto
+fn dijkstra(map: &Map, seeds: Vec<(usize, i32)>) -> Vec<i32> {
+    let mut cost = vec![i32::MAX; (map.width * map.height) as usize];
+    let mut open = BinaryHeap::new();
+    for (idx, start) in seeds {
+        cost[idx] = start;
+        open.push(Reverse((start, idx)));
+    }
+
+    while let Some(Reverse((current_cost, idx))) = open.pop() {
+        if current_cost > cost[idx] { continue; }
+
+        let cx = idx as i32 % map.width;
+        let cy = idx as i32 / map.width;
+        for (dx, dy) in NEIGHBOURS.iter() {
+            let (nx, ny) = (cx + dx, cy + dy);
+            if nx < 0 || nx >= map.width || ny < 0 || ny >= map.height { continue; }
+            let next = map.xy_idx(nx, ny);
+            if !map.tiles[next].is_passable() { continue; }
+
+            let step = if *dx != 0 && *dy != 0 { 145.0 } else { 100.0 };
+            let next_cost = current_cost + (step * map.movement_cost(next)) as i32;
+            if next_cost < cost[next] {
+                cost[next] = next_cost;
+                open.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    cost
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rltk::RandomNumberGenerator;
+    use std::time::Instant;
+
+    fn open_map(width: i32, height: i32) -> Map {
+        let size = (width * height) as usize;
+        let mut map = Map{
+            tiles: vec![TileType::Floor; size],
+            width,
+            height,
+            blocked: vec![false; size],
+            ..Map::default()
+        };
+        for x in 0 .. width {
+            for y in [0, height - 1].iter() {
+                let idx = map.xy_idx(x, *y);
+                map.tiles[idx] = TileType::Wall;
+            }
+        }
+        for y in 0 .. height {
+            for x in [0, width - 1].iter() {
+                let idx = map.xy_idx(*x, y);
+                map.tiles[idx] = TileType::Wall;
+            }
+        }
+        map.populate_blocked();
+        map
+    }
+
+    fn distance(map: &Map, idx: usize, to: Point) -> f32 {
+        let p = Point::new(idx as i32 % map.width, idx as i32 / map.width);
+        rltk::DistanceAlg::Pythagoras.distance2d(p, to)
+    }
+
+    #[test]
+    fn approach_moves_closer() {
+        let map = open_map(20, 20);
+        let player = Point::new(15, 10);
+        let flow = FlowField::new(&map, player);
+        let start = map.xy_idx(5, 10);
+        let step = flow.step_toward(&map, start).unwrap();
+        assert_eq!(step, map.xy_idx(6, 10));
+    }
+
+    #[test]
+    fn approach_goes_around_blocking_monster() {
+        let mut map = open_map(20, 20);
+        let player = Point::new(15, 10);
+        let flow = FlowField::new(&map, player);
+        let blocker = map.xy_idx(6, 10);
+        map.blocked[blocker] = true;
+        let start = map.xy_idx(5, 10);
+        let step = flow.step_toward(&map, start).unwrap();
+        assert_ne!(step, blocker);
+        assert!(distance(&map, step, player) < distance(&map, start, player));
+    }
+
+    #[test]
+    fn never_steps_onto_target() {
+        let map = open_map(10, 10);
+        let player = Point::new(5, 5);
+        let flow = FlowField::new(&map, player);
+        assert_eq!(flow.step_toward(&map, map.xy_idx(6, 5)), None);
+    }
+
+    #[test]
+    fn toward_steps_onto_target() {
+        let map = open_map(10, 10);
+        let last_seen = Point::new(5, 5);
+        let flow = FlowField::toward(&map, last_seen);
+        assert_eq!(flow.step_toward(&map, map.xy_idx(6, 5)), Some(map.xy_idx(5, 5)));
+    }
+
+    #[test]
+    fn approach_avoids_lava() {
+        let mut map = open_map(20, 20);
+        for y in 8 ..= 12 {
+            let idx = map.xy_idx(6, y);
+            map.tiles[idx] = TileType::Lava;
+        }
+        let player = Point::new(15, 10);
+        let flow = FlowField::new(&map, player);
+        let step = flow.step_toward(&map, map.xy_idx(5, 10)).unwrap();
+        assert!(map.tiles[step] != TileType::Lava);
+    }
+
+    #[test]
+    fn flee_moves_away() {
+        let map = open_map(20, 20);
+        let player = Point::new(10, 10);
+        let flow = FlowField::new(&map, player);
+        let start = map.xy_idx(11, 10);
+        let step = flow.step_away(&map, start).unwrap();
+        assert!(distance(&map, step, player) > distance(&map, start, player));
+    }
+
+    // Compares a search per monster with one shared field on a large crowded map.
+    // Run with `cargo test --release flow_field_benchmark -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn flow_field_benchmark() {
+        const MONSTERS: usize = 60;
+        const TURNS: u32 = 20;
+
+        let mut map = open_map(200, 200);
+        let mut rng = RandomNumberGenerator::seeded(7);
+        for _ in 0 .. 4000 {
+            let idx = map.xy_idx(rng.range(1, 199), rng.range(1, 199));
+            map.tiles[idx] = TileType::Wall;
+        }
+        map.populate_blocked();
+        let player = Point::new(100, 100);
+        let player_idx = map.xy_idx(player.x, player.y);
+        map.tiles[player_idx] = TileType::Floor;
+        map.blocked[player_idx] = false;
+
+        let mut monsters = Vec::new();
+        while monsters.len() < MONSTERS {
+            let idx = map.xy_idx(rng.range(1, 199), rng.range(1, 199));
+            if map.blocked[idx] || idx == player_idx { continue; }
+            map.blocked[idx] = true;
+            monsters.push(idx);
+        }
+
+        let before = Instant::now();
+        for _ in 0 .. TURNS {
+            for monster in monsters.iter() {
+                rltk::a_star_search(*monster as i32, player_idx as i32, &mut map);
+            }
+        }
+        let before = before.elapsed() / TURNS;
+
+        let after = Instant::now();
+        for _ in 0 .. TURNS {
+            let flow = FlowField::new(&map, player);
+            for monster in monsters.iter() {
+                flow.step_toward(&map, *monster);
+            }
+        }
+        let after = after.elapsed() / TURNS;
+
+        println!("{} monsters on a 200x200 map: a_star per monster {:?}/turn, shared flow field {:?}/turn",
+            MONSTERS, before, after);
+    }
+}
\ No newline at end of file
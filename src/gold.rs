@@ -0,0 +1,21 @@
+use serde::{Serialize, Deserialize};
+
+pub const STARTING_GOLD: i32 = 99;
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
+pub struct Gold {
+    pub amount: i32
+}
+
+impl Gold {
+    pub fn gain(&mut self, amount: i32) {
+        self.amount += amount;
+    }
+
+    // Takes up to the given amount, returning how much was actually taken
+    pub fn steal(&mut self, amount: i32) -> i32 {
+        let stolen = i32::min(amount, self.amount);
+        self.amount -= stolen;
+        stolen
+    }
+}
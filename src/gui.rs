@@ -4,9 +4,9 @@ use rltk::{RGB, Rltk, VirtualKeyCode};
 use std::char;
 
 use super::{
-    Map, Name, Position, Point, Gamelog, creature,
-    deck::Deck, util::utils, monsters, item, status,
-    map::MAPWIDTH, map::MAPHEIGHT, WINDOWWIDTH, WINDOWHEIGHT, deck::MAX_HAND_SIZE
+    Map, Name, Position, Point, Gamelog, Gold, act, creature,
+    deck::Deck, util::utils, effects, monsters, item, orb, passive, stance, status,
+    map::in_range, map::MAPWIDTH, map::MAPHEIGHT, WINDOWWIDTH, WINDOWHEIGHT, deck::MAX_HAND_SIZE
 };
 
 pub const GUISIZE: usize = 14;
@@ -54,7 +54,18 @@ fn draw_tooltips(ecs: &World, ctx: &mut Rltk) {
     }
 
     // Push enemy intent to tooltips
-    for (position, _, ac, stat) in (&positions, &monsters, &attack_cycles, &combat_stats).join() {
+    let sleeping = ecs.read_storage::<creature::Asleep>();
+    let intents = ecs.read_storage::<creature::Intent>();
+    let deal_damage = ecs.read_storage::<effects::DealDamage>();
+    let splits = ecs.read_storage::<creature::Splits>();
+    let awareness = ecs.read_storage::<creature::Awareness>();
+    let viewsheds = ecs.read_storage::<creature::Viewshed>();
+    let gremlins = ecs.read_storage::<creature::Gremlin>();
+    let entities = ecs.entities();
+    let allies: Vec<(Entity, Point, i32)> = (&entities, &positions, &combat_stats, &monsters).join()
+        .map(|(ent, pos, stats, _)| (ent, Point::new(pos.x, pos.y), stats.hp))
+        .collect();
+    for (ent, position, _, ac, intent, stat, asleep) in (&entities, &positions, &monsters, &attack_cycles, &intents, &combat_stats, sleeping.maybe()).join() {
         let idx = map.xy_idx(position.x, position.y);
         if position.x == mouse_pos.0 && position.y == mouse_pos.1 && map.visible_tiles[idx] {
             match ac.attacks[ac.cycle] {
@@ -85,6 +96,15 @@ fn draw_tooltips(ecs: &World, ctx: &mut Rltk) {
                 monsters::Attacks::ApplyFrail{name: _, range, turns} => {
                     tooltip.push(format!("{}:F{}", range, turns));
                 }
+                monsters::Attacks::ApplyEntangled{name: _, range, turns} => {
+                    tooltip.push(format!("{}:E{}", range, turns));
+                }
+                monsters::Attacks::ApplyRooted{name: _, range, turns} => {
+                    tooltip.push(format!("{}:R{}", range, turns));
+                }
+                monsters::Attacks::ApplySlowed{name: _, range, turns} => {
+                    tooltip.push(format!("{}:Sl{}", range, turns));
+                }
                 monsters::Attacks::BuffStrength{name: _, range:_, amount} => {
                     tooltip.push(format!("S{}", amount));
                 }
@@ -96,10 +116,78 @@ fn draw_tooltips(ecs: &World, ctx: &mut Rltk) {
                     let damage = amount + stat.strength;
                     tooltip.push(format!("{}:A{},#", range, damage));
                 }
+                monsters::Attacks::GiveCard{name: _, range, card: _, number: _} => {
+                    tooltip.push(format!("{}:#", range));
+                }
+                monsters::Attacks::DebuffStrengthAndDexterity{name: _, range, strength_amount, dexterity_amount} => {
+                    tooltip.push(format!("{}:S-{},D-{}", range, strength_amount, dexterity_amount));
+                }
+                monsters::Attacks::Divider{name: _, range, hits: _} => {
+                    // Divider's damage was fixed by the player's health when it was telegraphed
+                    let damage = deal_damage.get(intent.intent).map_or(0, |d| d.amount) + stat.strength;
+                    tooltip.push(format!("{}:A{}", range, damage));
+                }
+                monsters::Attacks::GainPassive{name: _, range: _, passive} => {
+                    tooltip.push(passive.to_name());
+                }
+                monsters::Attacks::AttackAndStealGold{name: _, range, amount, gold} => {
+                    let damage = amount + stat.strength;
+                    tooltip.push(format!("{}:A{},${}", range, damage, gold));
+                }
+                monsters::Attacks::BlockAndFlee{name: _, range: _, amount} => {
+                    let block = amount + stat.dexterity;
+                    tooltip.push(format!("{},Flee", block));
+                }
+                monsters::Attacks::BlockAlly{name: _, range, amount} => {
+                    // Show which ally the block is aimed at
+                    let supported = monsters::supported_allies(ent, &allies, &gremlins);
+                    let ally = viewsheds.get(ent).and_then(|v| monsters::weakest_ally_in_range(
+                        ent, Point::new(position.x, position.y), range, &v.visible_tiles, &supported));
+                    match ally.and_then(|(a, _, _)| names.get(a)) {
+                        Some(ally_name) => tooltip.push(format!("B{}>{}", amount, ally_name.name)),
+                        None => tooltip.push(format!("B{}", amount)),
+                    }
+                }
+                monsters::Attacks::BuffAllies{name: _, range, amount} => {
+                    // Name every ally the buff will reach
+                    let supported = monsters::supported_allies(ent, &allies, &gremlins);
+                    let targets: Vec<String> = viewsheds.get(ent).map_or(Vec::new(), |v| monsters::allies_in_range(
+                            ent, Point::new(position.x, position.y), range, &v.visible_tiles, &supported))
+                        .iter()
+                        .filter_map(|(a, _, _)| names.get(*a).map(|n| n.name.clone()))
+                        .collect();
+                    if targets.is_empty() {
+                        tooltip.push(format!("{}:S{}", range, amount));
+                    } else {
+                        tooltip.push(format!("{}:S{}>{}", range, amount, targets.join(",")));
+                    }
+                }
+                monsters::Attacks::AttackAndPush{name: _, range, amount, distance, collision_damage: _} => {
+                    let damage = amount + stat.strength;
+                    tooltip.push(format!("{}:A{},Push{}", range, damage, distance));
+                }
+                monsters::Attacks::Pull{name: _, range, distance} => {
+                    tooltip.push(format!("{}:Pull{}", range, distance));
+                }
+                monsters::Attacks::Swap{name: _, range} => {
+                    tooltip.push(format!("{}:Swap", range));
+                }
                 monsters::Attacks::Pass{name: _, range: _} => {
                     tooltip.push("???".to_string());
                 }
             }
+            if asleep.is_some() {
+                tooltip.pop();
+                tooltip.push("Zzz".to_string());
+            } else if splits.get(ent).map_or(false, |split| split.ready) {
+                tooltip.pop();
+                tooltip.push("Split".to_string());
+            } else if let Some(aware) = awareness.get(ent) {
+                // Follow the intent with how aware the monster is of the player
+                if let Some(intent_text) = tooltip.pop() {
+                    tooltip.push(format!("{} {}", intent_text, aware.state.to_name()));
+                }
+            }
             draw_intents = true;
         }
     }
@@ -110,6 +198,9 @@ fn draw_tooltips(ecs: &World, ctx: &mut Rltk) {
         let status_vulnerable = ecs.read_storage::<status::Vulnerable>();
         let status_frail = ecs.read_storage::<status::Frail>();
         let status_poison = ecs.read_storage::<status::Poison>();
+        let status_entangled = ecs.read_storage::<status::Entangled>();
+        let status_rooted = ecs.read_storage::<status::Rooted>();
+        let status_slowed = ecs.read_storage::<status::Slowed>();
         for (position, _, weak, vulnerable, frail, poison) in (&positions, &creatures, status_weak.maybe(),
                 status_vulnerable.maybe(), status_frail.maybe(), status_poison.maybe()).join() {
             let idx = map.xy_idx(position.x, position.y);
@@ -119,9 +210,31 @@ fn draw_tooltips(ecs: &World, ctx: &mut Rltk) {
                 if let Some(v) = frail { tooltip.push(format!("F{}", v.turns)); }
                 if let Some(p) = poison { tooltip.push(format!("P{}", p.turns)); }
             }
+        }
+        for (position, _, entangled, rooted, slowed) in (&positions, &creatures, status_entangled.maybe(),
+                status_rooted.maybe(), status_slowed.maybe()).join() {
+            let idx = map.xy_idx(position.x, position.y);
+            if position.x == mouse_pos.0 && position.y == mouse_pos.1 && map.visible_tiles[idx] {
+                if let Some(e) = entangled { tooltip.push(format!("E{}", e.turns)); }
+                if let Some(r) = rooted { tooltip.push(format!("R{}", r.turns)); }
+                if let Some(s) = slowed { tooltip.push(format!("Sl{}", s.turns)); }
+            }
         }        
     }
 
+    // Push passives to tooltips
+    {
+        let passives = ecs.read_storage::<passive::Passives>();
+        for (position, _, creature_passives) in (&positions, &creatures, &passives).join() {
+            let idx = map.xy_idx(position.x, position.y);
+            if position.x == mouse_pos.0 && position.y == mouse_pos.1 && map.visible_tiles[idx] {
+                for p in creature_passives.passives.iter() {
+                    tooltip.push(p.to_name());
+                }
+            }
+        }
+    }
+
     if !tooltip.is_empty() {
         let mut width: i32 = 0;
         for s in tooltip.iter() {
@@ -181,21 +294,22 @@ fn draw_tooltips(ecs: &World, ctx: &mut Rltk) {
     }
 }
 
-pub fn ranged_target(ecs: &World, ctx: &mut Rltk, range: i32, radius: i32) -> (ItemMenuResult, Option<Point>) {
+pub fn ranged_target(ecs: &World, ctx: &mut Rltk, action: Entity, range: i32) -> (ItemMenuResult, Option<Point>) {
     let player_entity = ecs.fetch::<Entity>();
     let player_pos = ecs.fetch::<Point>();
+    let map = ecs.fetch::<Map>();
     let viewsheds = ecs.read_storage::<creature::Viewshed>();
+    let creatures = ecs.read_storage::<creature::Creature>();
+    let projectile = ecs.read_storage::<item::Projectile>().get(action).is_some();
+    let aoe = ecs.read_storage::<item::AreaOfEffect>();
     let mouse_pos = ctx.mouse_pos();
     let mouse_point = Point::new(mouse_pos.0, mouse_pos.1);
 
-    let adjusted_range = range as f32 + { if range <= 1 { 0.5 } else { 0.0 } };
-
-    // Highlight available target cells
+    // Highlight available target cells, which need a line of fire that no wall blocks
     let mut available_cells = Vec::new();
     if let Some(visible) = viewsheds.get(*player_entity) {
         for idx in visible.visible_tiles.iter() {
-            let dist = rltk::DistanceAlg::Pythagoras.distance2d(*player_pos, *idx);
-            if dist <= adjusted_range as f32 {
+            if in_range(*player_pos, *idx, range) && map.has_line_of_fire(*player_pos, *idx, false) {
                 ctx.set_bg(idx.x, idx.y, RGB::named(rltk::YELLOW));
                 available_cells.push(idx);
             }
@@ -204,16 +318,26 @@ pub fn ranged_target(ecs: &World, ctx: &mut Rltk, range: i32, radius: i32) -> (I
         return (ItemMenuResult::Cancel, None);
     }
 
-    // Highlight radius for aoe attacks
+    // Highlight the path of the shot, and the exact tiles an aoe attack will affect
+    let mut first_hit = None;
     if available_cells.contains(&&mouse_point) {
-        if let Some(visible) = viewsheds.get(*player_entity) {
-            for idx in visible.visible_tiles.iter() {
-                let dist = rltk::DistanceAlg::Pythagoras.distance2d(mouse_point, *idx);
-                if dist <= radius as f32 {
-                    ctx.set_bg(idx.x, idx.y, RGB::named(rltk::CYAN));
-                }
+        let path = map.line_of_fire(*player_pos, mouse_point, projectile);
+        for tile in path.iter() {
+            ctx.set_bg(tile.x, tile.y, RGB::named(rltk::ORANGE));
+        }
+        let impact = path.last().copied().unwrap_or(mouse_point);
+
+        if let Some(area_effect) = aoe.get(action) {
+            for tile in area_effect.tiles(&*map, *player_pos, impact).iter() {
+                ctx.set_bg(tile.x, tile.y, RGB::named(rltk::CYAN));
             }
         }
+
+        // The creature the shot will hit first
+        let impact_idx = map.xy_idx(impact.x, impact.y);
+        if map.tile_content[impact_idx].iter().any(|ent| *ent != *player_entity && creatures.get(*ent).is_some()) {
+            first_hit = Some(impact);
+        }
     }
 
     // Draw mouse cursor
@@ -221,6 +345,9 @@ pub fn ranged_target(ecs: &World, ctx: &mut Rltk, range: i32, radius: i32) -> (I
     for idx in available_cells.iter() { if idx.x == mouse_pos.0 && idx.y == mouse_pos.1 { valid_target = true; } }
     if valid_target {
         ctx.set_bg(mouse_pos.0, mouse_pos.1, RGB::named(rltk::CYAN));
+        if let Some(hit) = first_hit {
+            ctx.set_bg(hit.x, hit.y, RGB::named(rltk::RED));
+        }
         if ctx.left_click {
             return (ItemMenuResult::Selected, Some(Point::new(mouse_pos.0, mouse_pos.1)));
         }
@@ -255,6 +382,26 @@ pub fn draw_ui(ecs: &World, ctx: &mut Rltk) {
         ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::GREEN), RGB::named(rltk::BLACK), &dexterity);
         x += dexterity.len() + 1;
     }
+    {
+        let gold = format!("${}", ecs.fetch::<Gold>().amount);
+        ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::GOLD), RGB::named(rltk::BLACK), &gold);
+        x += gold.len() + 1;
+
+        let keys = ecs.fetch::<Map>().keys;
+        if keys > 0 {
+            let keys_text = format!("Key{}", keys);
+            ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::GOLD), RGB::named(rltk::BLACK), &keys_text);
+            x += keys_text.len() + 1;
+        }
+    }
+    let stealth = ecs.read_storage::<creature::Stealth>();
+    for (_, player_stealth) in (&players, &stealth).join() {
+        if player_stealth.amount() > 0 {
+            let stealth_text = format!("H{}", player_stealth.amount());
+            ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::GRAY), RGB::named(rltk::BLACK), &stealth_text);
+            x += stealth_text.len() + 1;
+        }
+    }
 
     // Draw player status effects
     let status_weak = ecs.read_storage::<status::Weak>();
@@ -264,15 +411,101 @@ pub fn draw_ui(ecs: &World, ctx: &mut Rltk) {
         if let Some(w) = weak {
             let weak_text = format!("W{}", w.turns);
             ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::LIGHTBLUE), RGB::named(rltk::BLACK), &weak_text);
-            x += weak_text.len()
+            x += weak_text.len() + 1;
         }
         if let Some(v) = vulnerable {
             let vulnerable_text = format!("V{}", v.turns);
             ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::RED), RGB::named(rltk::BLACK), &vulnerable_text);
+            x += vulnerable_text.len() + 1;
         }
         if let Some(f) = frail {
             let frail_text = format!("F{}", f.turns);
             ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::TEAL), RGB::named(rltk::BLACK), &frail_text);
+            x += frail_text.len() + 1;
+        }
+    }
+
+    // Draw player movement statuses
+    let status_entangled = ecs.read_storage::<status::Entangled>();
+    let status_rooted = ecs.read_storage::<status::Rooted>();
+    let status_slowed = ecs.read_storage::<status::Slowed>();
+    for (_, entangled, rooted, slowed) in (&players, status_entangled.maybe(), status_rooted.maybe(), status_slowed.maybe()).join() {
+        if let Some(e) = entangled {
+            let entangled_text = format!("E{}", e.turns);
+            ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::OLIVE), RGB::named(rltk::BLACK), &entangled_text);
+            x += entangled_text.len() + 1;
+        }
+        if let Some(r) = rooted {
+            let rooted_text = format!("R{}", r.turns);
+            ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::BROWN1), RGB::named(rltk::BLACK), &rooted_text);
+            x += rooted_text.len() + 1;
+        }
+        if let Some(s) = slowed {
+            let slowed_text = format!("Sl{}", s.turns);
+            ctx.print_color(x, MAPHEIGHT, RGB::named(rltk::GRAY), RGB::named(rltk::BLACK), &slowed_text);
+            x += slowed_text.len() + 1;
+        }
+    }
+
+    // Draw player stance on the right of the gui box
+    let in_stance = ecs.read_storage::<stance::InStance>();
+    for (_, current) in (&players, &in_stance).join() {
+        let stance_text = format!(" {} ", current.stance.to_name().to_uppercase());
+        let stance_x = MAPWIDTH - INVENTORYWIDTH - 2 - stance_text.len();
+        ctx.print_color(stance_x, MAPHEIGHT, RGB::named(rltk::BLACK), current.stance.color(), &stance_text);
+    }
+
+    // Draw player orb slots along the bottom of the gui box
+    let orb_slots = ecs.read_storage::<orb::OrbSlots>();
+    for (_, slots) in (&players, &orb_slots).join() {
+        let mut x = 2;
+        let y = WINDOWHEIGHT - 1;
+        ctx.print_color(x, y, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), "Orbs");
+        x += 5;
+        for i in 0 .. slots.max_slots {
+            let (orb_text, color) = match slots.orbs.get(i) {
+                Some(orb::Orb::Dark{ damage }) => (format!("D{}", damage), orb::Orb::Dark{ damage: *damage }.color()),
+                Some(o) => (o.glyph().to_string(), o.color()),
+                None => ("o".to_string(), RGB::named(rltk::DARK_GRAY)),
+            };
+            ctx.print_color(x, y, color, RGB::named(rltk::BLACK), &orb_text);
+            x += orb_text.len() + 1;
+        }
+    }
+
+    // Draw act and floor along the bottom of the gui box
+    {
+        let map = ecs.fetch::<Map>();
+        let current_act = act::Act::from_floor(map.depth);
+        let floor_text = if act::is_boss_floor(map.depth) {
+            format!(" Act {}: {} - Boss ", current_act.number(), current_act.to_name())
+        } else {
+            format!(" Act {}: {} - Floor {} ", current_act.number(), current_act.to_name(), act::floor_in_act(map.depth))
+        };
+        let floor_x = MAPWIDTH - INVENTORYWIDTH - 2 - floor_text.len();
+        ctx.print_color(floor_x, WINDOWHEIGHT - 1, current_act.color(), RGB::named(rltk::BLACK), &floor_text);
+    }
+
+    // Draw one health bar across the top of the map for the bosses in view,
+    // so a boss that splits keeps a single bar shared by its halves
+    {
+        let map = ecs.fetch::<Map>();
+        let names = ecs.read_storage::<Name>();
+        let positions = ecs.read_storage::<Position>();
+        let bosses = ecs.read_storage::<creature::Boss>();
+        let in_view: Vec<(&Name, &creature::CombatStats)> = (&bosses, &names, &positions, &combat_stats).join()
+            .filter(|(_, _, pos, _)| map.visible_tiles[map.xy_idx(pos.x, pos.y)])
+            .map(|(_, name, _, stats)| (name, stats))
+            .collect();
+
+        if !in_view.is_empty() {
+            let hp: i32 = in_view.iter().map(|(_, stats)| stats.hp).sum();
+            let max_hp: i32 = in_view.iter().map(|(_, stats)| stats.max_hp).sum();
+            let label = if in_view.len() == 1 { in_view[0].0.name.to_string() } else { format!("Bosses ({})", in_view.len()) };
+            let boss_text = format!(" {} {}/{} ", label, hp, max_hp);
+            ctx.print_color(1, 0, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), &boss_text);
+            let bar_x = 1 + boss_text.len();
+            ctx.draw_bar_horizontal(bar_x, 0, MAPWIDTH - bar_x - 2, hp, max_hp, RGB::named(rltk::RED), RGB::named(rltk::BLACK));
         }
     }
 
@@ -294,6 +527,7 @@ pub fn draw_ui(ecs: &World, ctx: &mut Rltk) {
 pub fn draw_hand(ecs: &World, ctx: &mut Rltk)  {
     let deck = ecs.write_resource::<Deck>();
     let cards = ecs.read_storage::<item::Card>();
+    let unplayable = ecs.read_storage::<item::Unplayable>();
     let names = ecs.read_storage::<Name>();
 
     let player_entity = ecs.fetch::<Entity>();
@@ -313,14 +547,15 @@ pub fn draw_hand(ecs: &World, ctx: &mut Rltk)  {
     let mut i = 1;
     for c in deck.hand.iter() {
         let card_cost = cards.get(*c).unwrap().energy_cost;
-        let card_cost_color = if card_cost <= player_energy.energy { RGB::named(rltk::YELLOW) } else { RGB::named(rltk::DARK_GRAY) };
+        let playable = unplayable.get(*c).is_none();
+        let card_cost_color = if playable && card_cost <= player_energy.energy { RGB::named(rltk::YELLOW) } else { RGB::named(rltk::DARK_GRAY) };
 
         ctx.set(INVENTORYPOS + 2, y + 2, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), rltk::to_cp437('('));
         ctx.set(INVENTORYPOS + 3, y + 2, card_cost_color, RGB::named(rltk::BLACK), rltk::to_cp437(char::from_digit(i % 10, 10).unwrap()));
         ctx.set(INVENTORYPOS + 4, y + 2, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), rltk::to_cp437(')'));
 
         ctx.print(INVENTORYPOS + 6, y + 2, names.get(*c).unwrap().name.to_string());
-        ctx.print(INVENTORYPOS + 23, y + 2, card_cost.to_string());
+        if playable { ctx.print(INVENTORYPOS + 23, y + 2, card_cost.to_string()); }
         hand.push(*c);
         y += 1;
         i += 1;
@@ -329,6 +564,7 @@ pub fn draw_hand(ecs: &World, ctx: &mut Rltk)  {
 
 pub fn pick_card(ecs: &World, selection: i32) -> (ItemMenuResult, Option<Entity>) {
     let cards = ecs.read_storage::<item::Card>();
+    let unplayable = ecs.read_storage::<item::Unplayable>();
     let deck = ecs.write_resource::<Deck>();
     let hand = &deck.hand;
 
@@ -337,7 +573,8 @@ pub fn pick_card(ecs: &World, selection: i32) -> (ItemMenuResult, Option<Entity>
     let player_energy = players.get(*player_entity).unwrap();
 
     if selection > -1 && selection < hand.len() as i32
-        && cards.get(hand[selection as usize]).unwrap().energy_cost <= player_energy.energy {
+        && cards.get(hand[selection as usize]).unwrap().energy_cost <= player_energy.energy
+        && unplayable.get(hand[selection as usize]).is_none() {
         return (ItemMenuResult::Selected, Some(hand[selection as usize]));
     }
     (ItemMenuResult::Cancel, None)
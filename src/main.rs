@@ -11,6 +11,10 @@ use components::component::{Name, Position, Renderable};
 use components::creature;
 use components::effects;
 use components::item;
+use components::orb;
+use components::passive;
+use components::relic;
+use components::stance;
 use components::status;
 
 mod gui;
@@ -18,11 +22,18 @@ mod menu;
 
 mod gamelog;
 use gamelog::Gamelog;
+mod gold;
+use gold::Gold;
 mod map;
 use map::Map;
+mod map_builders;
+mod flowfield;
+mod noise;
 mod player;
 
+mod act;
 mod cards;
+mod character;
 mod deck;
 mod monsters;
 mod spawner;
@@ -38,12 +49,6 @@ pub const WINDOWWIDTH: usize = 80;
 pub const WINDOWHEIGHT: usize = 50;
 
 fn main() -> rltk::BError {
-    use rltk::RltkBuilder;
-    let mut context = RltkBuilder::simple80x50()
-        .with_title("spire_rogue")
-        .build()?;
-    context.with_post_scanlines(true);
-
     // Create gamestate and register <RunState> resource
     let mut gs = State{ ecs: World::new() };
     gs.ecs.insert(RunState::MainMenu{ menu_selection: menu::MainMenuSelection::NewGame });
@@ -54,6 +59,18 @@ fn main() -> rltk::BError {
     // Register <GameLog> resource
     gs.ecs.insert(Gamelog{ entries: Vec::new() });
 
+    // Register <Gold> resource for the player's purse
+    gs.ecs.insert(Gold::default());
+
+    // Register <spawner::RewardQueue> resource for rewards dropped by elites
+    gs.ecs.insert(spawner::RewardQueue::default());
+    // Register <spawner::SplitQueue> resource for monsters splitting mid-combat
+    gs.ecs.insert(spawner::SplitQueue::default());
+
+    // Register <noise::NoiseEvents> and <noise::NoiseField> resources for noises monsters can hear
+    gs.ecs.insert(noise::NoiseEvents::default());
+    gs.ecs.insert(noise::NoiseField::default());
+
     // Register serialize marker resource
     gs.ecs.insert(SimpleMarkerAllocator::<saveload::SerializeMe>::new());
 
@@ -65,15 +82,31 @@ fn main() -> rltk::BError {
     gs.ecs.register::<creature::Creature>();
     gs.ecs.register::<creature::Player>();
     gs.ecs.register::<creature::Monster>();
+    gs.ecs.register::<creature::Boss>();
+    gs.ecs.register::<creature::Elite>();
+    gs.ecs.register::<creature::EliteGroup>();
+    gs.ecs.register::<creature::Gremlin>();
+    gs.ecs.register::<creature::Stationary>();
+    gs.ecs.register::<creature::Enrage>();
+    gs.ecs.register::<creature::Asleep>();
+    gs.ecs.register::<creature::Splits>();
+    gs.ecs.register::<creature::ModeShift>();
+    gs.ecs.register::<creature::Awareness>();
+    gs.ecs.register::<creature::Stealth>();
+    gs.ecs.register::<creature::Thief>();
+    gs.ecs.register::<creature::Fleeing>();
+    gs.ecs.register::<creature::Behaviour>();
     gs.ecs.register::<creature::CombatStats>();
     gs.ecs.register::<creature::BlocksTile>();
     gs.ecs.register::<creature::Viewshed>();
     gs.ecs.register::<creature::SufferDamage>();
+    gs.ecs.register::<creature::EntityMoved>();
     gs.ecs.register::<creature::PerformAction>();
     gs.ecs.register::<creature::PickupItem>();
     gs.ecs.register::<creature::Attack>();
     gs.ecs.register::<creature::Intent>();
     gs.ecs.register::<creature::AttackCycle>();
+    gs.ecs.register::<creature::InCombat>();
 
     gs.ecs.register::<SimpleMarker<saveload::SerializeMe>>();
     gs.ecs.register::<saveload::SerializableResources>();
@@ -86,52 +119,79 @@ fn main() -> rltk::BError {
     gs.ecs.register::<effects::GainCard>();
     gs.ecs.register::<effects::BuffStrength>();
     gs.ecs.register::<effects::BuffDexterity>();
+    gs.ecs.register::<effects::DebuffStrength>();
+    gs.ecs.register::<effects::DebuffDexterity>();
     gs.ecs.register::<effects::Teleport>();
+    gs.ecs.register::<effects::LoseHp>();
+    gs.ecs.register::<effects::GainEnergy>();
+    gs.ecs.register::<effects::GainStealth>();
+    gs.ecs.register::<effects::GrantBlock>();
+    gs.ecs.register::<effects::GrantStrength>();
+    gs.ecs.register::<effects::StealGold>();
+    gs.ecs.register::<effects::Flee>();
+    gs.ecs.register::<effects::Push>();
+    gs.ecs.register::<effects::Pull>();
+    gs.ecs.register::<effects::Swap>();
+    gs.ecs.register::<effects::CreateTerrain>();
+    gs.ecs.register::<effects::RevealTraps>();
+    gs.ecs.register::<effects::HealAfterCombat>();
+    gs.ecs.register::<effects::ChannelOrb>();
+    gs.ecs.register::<effects::EvokeOrb>();
+    gs.ecs.register::<effects::GainOrbSlots>();
+    gs.ecs.register::<effects::ChangeStance>();
+    gs.ecs.register::<effects::GainPassive>();
 
     gs.ecs.register::<item::Item>();
     gs.ecs.register::<item::Potion>();
     gs.ecs.register::<item::InBackpack>();
     gs.ecs.register::<item::Card>();
+    gs.ecs.register::<item::CardKind>();
+    gs.ecs.register::<item::GoldPile>();
+    gs.ecs.register::<item::Key>();
     gs.ecs.register::<item::Ethereal>();
     gs.ecs.register::<item::Fragile>();
+    gs.ecs.register::<item::Unplayable>();
     gs.ecs.register::<item::Targeted>();
+    gs.ecs.register::<item::TargetsAllies>();
+    gs.ecs.register::<item::Projectile>();
     gs.ecs.register::<item::AreaOfEffect>();
 
     gs.ecs.register::<status::Weak>();
     gs.ecs.register::<status::Vulnerable>();
     gs.ecs.register::<status::Frail>();
+    gs.ecs.register::<status::Entangled>();
+    gs.ecs.register::<status::Rooted>();
+    gs.ecs.register::<status::Slowed>();
     gs.ecs.register::<status::Poison>();
 
-    // Create map, mark player spawn position
-    let map = Map::new_map_rooms_and_corridors(1);
-    let (player_x, player_y) = map.rooms[0].center();
-
-    // Register player position <Point> resource
-    gs.ecs.insert(Point::new(player_x, player_y));
-
-    // Create player entity and register player <Entity> resource
-    let player_entity = spawner::player(&mut gs.ecs, player_x, player_y);
-    gs.ecs.insert(player_entity);
-
-    // Spawn mobs
-    for room in map.rooms.iter().skip(1) {
-        spawner::spawn_room(&mut gs.ecs, room, 1);
+    gs.ecs.register::<relic::Relics>();
+    gs.ecs.register::<orb::OrbSlots>();
+    gs.ecs.register::<stance::InStance>();
+    gs.ecs.register::<passive::Passives>();
+
+    // Register empty <Map>, <deck::Deck>, player position <Point> and player <Entity> resources,
+    // which are replaced when a game is started or loaded
+    gs.ecs.insert(Map::default());
+    gs.ecs.insert(deck::Deck::default());
+    gs.ecs.insert(Point::new(0, 0));
+    let placeholder = gs.ecs.create_entity().build();
+    gs.ecs.insert(placeholder);
+
+    // Convert a save file between formats with `spire_rogue convert <from> <to>`
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() == 4 && args[1] == "convert" {
+        if let Err(e) = saveload::convert_save(&mut gs.ecs, &args[2], &args[3]) {
+            eprintln!("Unable to convert {} to {}: {}", args[2], args[3], e);
+            std::process::exit(1);
+        }
+        return Ok(());
     }
 
-    // Create deck and register <deck::Deck> resource
-    let mut deck = deck::Deck{
-        hand: Vec::new(),
-        draw: Vec::new(),
-        discard: Vec::new(),
-    };
-    deck.gain_multiple_cards(cards::silent::starter(&mut gs.ecs));
-    for _ in 0 .. 5 {
-        deck.draw_card();
-    }
-    gs.ecs.insert(deck);
-
-    // Register <Map> resource
-    gs.ecs.insert(map);
+    use rltk::RltkBuilder;
+    let mut context = RltkBuilder::simple80x50()
+        .with_title("spire_rogue")
+        .build()?;
+    context.with_post_scanlines(true);
 
     rltk::main_loop(context, gs)
 }
@@ -1,21 +1,126 @@
 use specs::prelude::*;
 use serde::{Serialize, Deserialize};
 use rltk::{RGB, Rltk, RandomNumberGenerator, Algorithm2D, BaseMap, Point};
+use std::collections::HashMap;
 
-use super::{util::Rect, util::line};
+use super::{act, util::Rect, util::line};
 use super::{WINDOWWIDTH, WINDOWHEIGHT, gui::GUISIZE};
 
 pub const MAPWIDTH: usize = WINDOWWIDTH;
 pub const MAPHEIGHT: usize = WINDOWHEIGHT - GUISIZE;
-pub const MAPSIZE: usize = MAPHEIGHT * MAPWIDTH;
 
 #[derive(PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub enum TileType {
     Wall,
     Floor,
-    DownStairs
+    DownStairs,
+    // Slows creatures wading through it
+    ShallowWater,
+    // Too deep to walk through
+    DeepWater,
+    // Blocks vision but not movement
+    TallGrass,
+    // Burns creatures that step on it
+    Lava,
+    // Slows creatures climbing over it
+    Rubble,
+    // Blocks vision and movement until walked into
+    ClosedDoor,
+    OpenDoor,
+    // Opened with a key dropped by the floor's elite
+    LockedDoor,
 }
 
+impl TileType {
+    /// Cost of stepping onto the tile relative to open floor, used when pathing
+    pub fn movement_cost(self) -> f32 {
+        match self {
+            TileType::ShallowWater | TileType::Rubble | TileType::ClosedDoor => 2.0,
+            TileType::Lava => 8.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Returns true if creatures can stand on the tile
+    pub fn is_walkable(self) -> bool {
+        !matches!(self, TileType::Wall | TileType::DeepWater | TileType::ClosedDoor | TileType::LockedDoor)
+    }
+
+    /// Returns true if monsters can path through the tile, opening doors on the way
+    pub fn is_passable(self) -> bool {
+        self.is_walkable() || self == TileType::ClosedDoor
+    }
+
+    /// Returns true for walls and shut doors, which stop shots and spreading terrain
+    pub fn is_solid(self) -> bool {
+        matches!(self, TileType::Wall | TileType::ClosedDoor | TileType::LockedDoor)
+    }
+}
+
+/// Terrain laid over the floor by cards and potions, lasting a few rounds
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum TileEffectType {
+    // Damages creatures that step on it
+    Caltrops,
+    // Blocks vision
+    Smoke,
+    // Poisons creatures standing in it every round
+    PoisonGas,
+    // Creatures that step on it slip and cannot move on their next turn
+    Oil,
+}
+
+impl TileEffectType {
+    pub fn to_name(self) -> String {
+        match self {
+            TileEffectType::Caltrops => "Caltrops".to_string(),
+            TileEffectType::Smoke => "Smoke".to_string(),
+            TileEffectType::PoisonGas => "Poison Gas".to_string(),
+            TileEffectType::Oil => "Oil".to_string(),
+        }
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct TileEffect {
+    pub effect: TileEffectType,
+    pub turns: i32,
+}
+
+/// Traps set into the floor, going off whenever a creature steps on them
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum TrapType {
+    // Damages the creature
+    Spike,
+    // Makes a loud noise that draws monsters in
+    Alarm,
+    // Sends the creature somewhere else on the floor
+    Teleport,
+    // Shuffles a curse into the player's deck
+    Curse,
+}
+
+impl TrapType {
+    pub fn to_name(self) -> String {
+        match self {
+            TrapType::Spike => "Spike Trap".to_string(),
+            TrapType::Alarm => "Alarm Trap".to_string(),
+            TrapType::Teleport => "Teleport Trap".to_string(),
+            TrapType::Curse => "Curse Trap".to_string(),
+        }
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Trap {
+    pub trap: TrapType,
+    // Hidden traps are not drawn until the player finds them
+    pub revealed: bool,
+}
+
+// Extra pathing cost that keeps monsters off traps once they have been revealed
+const TRAP_AVOID_COST: f32 = 10.0;
+
 #[derive(Default, Serialize, Deserialize, Clone)]
 pub struct Map {
     pub tiles: Vec<TileType>,
@@ -23,16 +128,38 @@ pub struct Map {
     pub width: i32,
     pub height: i32,
     pub revealed_tiles: Vec<bool>,
+    pub depth: i32,
+
+    #[serde(default)]
+    pub elite_rooms: Vec<Rect>,
+
+    // Keyed by tile index, with at most one effect on each tile
+    #[serde(default)]
+    pub tile_effects: HashMap<usize, TileEffect>,
+
+    // Keys the player has picked up on this floor, each opening one locked door
+    #[serde(default)]
+    pub keys: i32,
+
+    // Keyed by tile index
+    #[serde(default)]
+    pub traps: HashMap<usize, Trap>,
+
+    #[serde(skip_serializing)]
+    #[serde(skip_deserializing)]
     pub visible_tiles: Vec<bool>,
+
+    #[serde(skip_serializing)]
+    #[serde(skip_deserializing)]
     pub blocked: Vec<bool>,
-    pub depth: i32,
 
     #[serde(skip_serializing)]
     #[serde(skip_deserializing)]
     pub tile_content: Vec<Vec<Entity>>,
 }
 
-fn build_map(width: i32, height: i32, default_tile: TileType, map_depth: i32) -> Map {
+/// Creates a map filled with a single tile type, for map builders to carve into
+pub fn build_map(width: i32, height: i32, default_tile: TileType, map_depth: i32) -> Map {
     let size = (width * height) as usize;
     Map{
         tiles: vec![default_tile; size],
@@ -44,6 +171,10 @@ fn build_map(width: i32, height: i32, default_tile: TileType, map_depth: i32) ->
         blocked: vec![false; size],
         tile_content: vec![Vec::new(); size],
         depth: map_depth,
+        elite_rooms: Vec::new(),
+        tile_effects: HashMap::new(),
+        keys: 0,
+        traps: HashMap::new(),
     }
 }
 
@@ -58,13 +189,22 @@ impl Map {
         }
     }
 
+    /// Rebuilds the tile data that is not saved with the map
+    pub fn rebuild_derived(&mut self) {
+        let size = (self.width * self.height) as usize;
+        self.visible_tiles = vec![false; size];
+        self.blocked = vec![false; size];
+        self.tile_content = vec![Vec::new(); size];
+        self.populate_blocked();
+    }
+
     pub fn populate_blocked(&mut self) {
         for (i, tile) in self.tiles.iter_mut().enumerate() {
-            self.blocked[i] = *tile == TileType::Wall;
+            self.blocked[i] = !tile.is_walkable();
         }
     }
 
-    fn apply_room(&mut self, room: &Rect) {
+    pub fn apply_room(&mut self, room: &Rect) {
         for y in room.y1 + 1 ..= room.y2 {
             for x in room.x1 + 1 ..= room.x2 {
                 let idx = self.xy_idx(x, y);
@@ -73,14 +213,14 @@ impl Map {
         }
     }
     
-    fn apply_euclidean_corridor(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
+    pub fn apply_euclidean_corridor(&mut self, x1: i32, y1: i32, x2: i32, y2: i32) {
         for (x, y) in line(x1, y1, x2, y2) {
             let idx = self.xy_idx(x, y);
             self.tiles[idx] = TileType::Floor;
         }
     }
 
-    fn apply_manhattan_corridor(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, right: bool) {
+    pub fn apply_manhattan_corridor(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, right: bool) {
         if right {
             self.apply_euclidean_corridor(x1, y1, x2, y1);
             self.apply_euclidean_corridor(x2, y1, x2, y2);
@@ -90,99 +230,261 @@ impl Map {
         }
     }
 
-    pub fn new_map_rooms_and_corridors(map_depth: i32) -> Map {
-        let mut map = build_map(MAPWIDTH as i32, MAPHEIGHT as i32, TileType::Wall, map_depth);
+    /// Scatters grass, rubble and, after the first act, embers through the rooms away from the
+    /// player's start and the stairs. Layouts built from proper rooms can also hold ponds.
+    pub fn apply_terrain_features(&mut self, rng: &mut RandomNumberGenerator, ponds: bool) {
+        let act = act::Act::from_floor(self.depth).number();
+        let rooms = self.rooms.clone();
+        for room in rooms.iter().skip(1) {
+            let holds_stairs = (room.y1 + 1 ..= room.y2)
+                .any(|y| (room.x1 + 1 ..= room.x2).any(|x| self.tiles[self.xy_idx(x, y)] == TileType::DownStairs));
+            if holds_stairs { continue; }
 
-        const MAX_ROOMS: i32 = 30;
-        const MIN_SIZE: i32 = 6;
-        const MAX_SIZE:i32 = 10;
+            match rng.roll_dice(1, 6) {
+                1 if ponds => self.apply_pond(room),
+                2 => self.apply_patch(rng, room, TileType::TallGrass, 14),
+                3 => self.apply_patch(rng, room, TileType::Rubble, 8),
+                4 if act > 1 => self.apply_patch(rng, room, TileType::Lava, 6),
+                _ => {}
+            }
+        }
+    }
 
-        let mut rng = RandomNumberGenerator::new();
+    // Fills a room with deep water ringed by shallows, leaving a border of floor
+    // so that corridors into the room stay connected
+    fn apply_pond(&mut self, room: &Rect) {
+        let (x1, x2, y1, y2) = (room.x1 + 2, room.x2 - 1, room.y1 + 2, room.y2 - 1);
+        for y in y1 ..= y2 {
+            for x in x1 ..= x2 {
+                let edge_x = x == x1 || x == x2;
+                let edge_y = y == y1 || y == y2;
+                if edge_x && edge_y { continue; }
 
-        for _ in 0 .. MAX_ROOMS {
-            let w = rng.range(MIN_SIZE, MAX_SIZE);
-            let h = rng.range(MIN_SIZE, MAX_SIZE);
-            let x = rng.roll_dice(1, map.width - w - 1) - 1;
-            let y = rng.roll_dice(1, map.height - h - 1) - 1;
-            let new_room = Rect::new(x, y, w, h);
-            let mut ok = true;
-            for other_room in map.rooms.iter() {
-                if new_room.intersect(other_room) { ok = false }
+                let idx = self.xy_idx(x, y);
+                if self.tiles[idx] != TileType::Floor { continue; }
+                self.tiles[idx] = if edge_x || edge_y { TileType::ShallowWater } else { TileType::DeepWater };
             }
-    
-            if ok {
-                map.apply_room(&new_room);
-    
-                if !map.rooms.is_empty() {
-                    let (new_x, new_y) = new_room.center();
-                    let (prev_x, prev_y) = map.rooms[map.rooms.len()-1].center();
-    
-                    if rng.range(0, 2) == 0 {
-                        map.apply_manhattan_corridor(new_x, new_y, prev_x, prev_y, true);
-                    } else {
-                        map.apply_manhattan_corridor(new_x, new_y, prev_x, prev_y, false);
-                    }
-                }
-    
-                map.rooms.push(new_room);
+        }
+    }
+
+    // Wanders around a room from a random point, turning floor into the given tile
+    fn apply_patch(&mut self, rng: &mut RandomNumberGenerator, room: &Rect, tile: TileType, size: i32) {
+        let mut x = rng.range(room.x1 + 1, room.x2 + 1);
+        let mut y = rng.range(room.y1 + 1, room.y2 + 1);
+        for _ in 0 .. size {
+            let idx = self.xy_idx(x, y);
+            if self.tiles[idx] == TileType::Floor {
+                self.tiles[idx] = tile;
+            }
+            x = i32::max(room.x1 + 1, i32::min(room.x2, x + rng.range(-1, 2)));
+            y = i32::max(room.y1 + 1, i32::min(room.y2, y + rng.range(-1, 2)));
+        }
+    }
+
+    /// Places a door wherever a corridor enters a room through a one tile gap in its wall
+    pub fn apply_doors(&mut self) {
+        let rooms = self.rooms.clone();
+        for room in rooms.iter() {
+            for x in room.x1 + 1 ..= room.x2 {
+                self.apply_door(x, room.y1, true);
+                self.apply_door(x, room.y2 + 1, true);
             }
+            for y in room.y1 + 1 ..= room.y2 {
+                self.apply_door(room.x1, y, false);
+                self.apply_door(room.x2 + 1, y, false);
+            }
+        }
+    }
+
+    fn apply_door(&mut self, x: i32, y: i32, horizontal_wall: bool) {
+        if x < 1 || x >= self.width - 1 || y < 1 || y >= self.height - 1 { return; }
+        let idx = self.xy_idx(x, y);
+        if self.tiles[idx] != TileType::Floor || self.is_lit(x, y) { return; }
+
+        let w = self.width as usize;
+        let (side_a, side_b) = if horizontal_wall { (idx - 1, idx + 1) } else { (idx - w, idx + w) };
+        if self.tiles[side_a] == TileType::Wall && self.tiles[side_b] == TileType::Wall {
+            self.tiles[idx] = TileType::ClosedDoor;
         }
+    }
 
-        // Place down stairs in the last room
-        let stairs_position = map.rooms[map.rooms.len() - 1].center();
-        let stairs_idx = map.xy_idx(stairs_position.0, stairs_position.1);
-        map.tiles[stairs_idx] = TileType::DownStairs;
+    /// Hides traps on open floor around the map, away from the player's starting room
+    pub fn apply_traps(&mut self, rng: &mut RandomNumberGenerator) {
+        let num_traps = 3 + self.depth / 2;
+        let start = self.rooms[0];
+        let mut tries = 0;
+        while (self.traps.len() as i32) < num_traps && tries < 1000 {
+            tries += 1;
+            let x = rng.range(1, self.width - 1);
+            let y = rng.range(1, self.height - 1);
+            let idx = self.xy_idx(x, y);
+            if self.tiles[idx] != TileType::Floor || self.traps.contains_key(&idx) { continue; }
+            if x >= start.x1 && x <= start.x2 + 1 && y >= start.y1 && y <= start.y2 + 1 { continue; }
+
+            let trap = match rng.roll_dice(1, 10) {
+                1 ..= 4 => TrapType::Spike,
+                5 ..= 7 => TrapType::Alarm,
+                8 ..= 9 => TrapType::Teleport,
+                _ => TrapType::Curse,
+            };
+            self.traps.insert(idx, Trap{ trap, revealed: false });
+        }
+    }
 
-        map
+    /// Reveals the hidden traps the player can see within `radius` of a point, returning how many were found
+    pub fn reveal_traps(&mut self, center: Point, radius: i32) -> i32 {
+        let width = self.width;
+        let visible = &self.visible_tiles;
+        let mut found = 0;
+        for (idx, trap) in self.traps.iter_mut() {
+            let point = Point::new(*idx as i32 % width, *idx as i32 / width);
+            if trap.revealed || !visible[*idx] { continue; }
+            if rltk::DistanceAlg::Pythagoras.distance2d(center, point) <= radius as f32 {
+                trap.revealed = true;
+                found += 1;
+            }
+        }
+        found
     }
 
-    pub fn new_map_field(map_depth: i32) -> Map {
-        let mut map = build_map(MAPWIDTH as i32, MAPHEIGHT as i32, TileType::Floor, map_depth);
+    /// Locks the doors of one of the candidate rooms, as long as every way into it is a door and
+    /// locking it leaves the stairs and every other room reachable. Returns the locked room.
+    pub fn lock_vault(&mut self, rng: &mut RandomNumberGenerator, candidates: &[usize]) -> Option<usize> {
+        if candidates.is_empty() { return None; }
+        let offset = rng.range(0, candidates.len() as i32) as usize;
+        for i in 0 .. candidates.len() {
+            let room_idx = candidates[(i + offset) % candidates.len()];
+            let room = self.rooms[room_idx];
+            let entrances = self.room_entrances(&room);
+            if entrances.is_empty() || entrances.iter().any(|idx| self.tiles[*idx] != TileType::ClosedDoor) {
+                continue;
+            }
 
-        let room = Rect::new(0, 0, map.width, map.height);
-        map.rooms.push(room);
-    
-        for x in 0 .. map.width {
-            let idx_1 = map.xy_idx(x, 0);
-            let idx_2 = map.xy_idx(x, map.height - 1);
-            map.tiles[idx_1] = TileType::Wall;
-            map.tiles[idx_2] = TileType::Wall;
+            for idx in entrances.iter() {
+                self.tiles[*idx] = TileType::LockedDoor;
+            }
+            if self.all_reachable_except(room_idx) {
+                return Some(room_idx);
+            }
+            for idx in entrances.iter() {
+                self.tiles[*idx] = TileType::ClosedDoor;
+            }
         }
-    
-        for y in 0 .. map.height {
-            let idx_1 = map.xy_idx(0, y);
-            let idx_2 = map.xy_idx(map.width - 1, y);
-            map.tiles[idx_1] = TileType::Wall;
-            map.tiles[idx_2] = TileType::Wall;
+        None
+    }
+
+    // Tiles in a room's walls that can be walked through
+    fn room_entrances(&self, room: &Rect) -> Vec<usize> {
+        let mut entrances = Vec::new();
+        for y in room.y1 ..= room.y2 + 1 {
+            for x in room.x1 ..= room.x2 + 1 {
+                let on_wall = x == room.x1 || x == room.x2 + 1 || y == room.y1 || y == room.y2 + 1;
+                if !on_wall || x < 0 || x >= self.width || y < 0 || y >= self.height { continue; }
+                let idx = self.xy_idx(x, y);
+                if self.tiles[idx] != TileType::Wall {
+                    entrances.push(idx);
+                }
+            }
         }
-    
-        let mut rng = RandomNumberGenerator::new();
-    
-        for _i in 0 .. (map.width * map.height) {
-            let x = rng.roll_dice(1, map.width - 1);
-            let y = rng.roll_dice(1, map.height - 1);
-            let idx = map.xy_idx(x, y);
-            let center = map.rooms[0].center();
-            if idx != map.xy_idx(center.0, center.1) {
-                map.tiles[idx] = TileType::Wall;
+        entrances
+    }
+
+    // Returns true if the stairs and every room but the skipped one can be reached from the player's start
+    fn all_reachable_except(&self, skip: usize) -> bool {
+        let (start_x, start_y) = self.rooms[0].center();
+        let start = self.xy_idx(start_x, start_y);
+        let mut reached = vec![false; self.tiles.len()];
+        let mut open = vec![start];
+        reached[start] = true;
+        while let Some(idx) = open.pop() {
+            let x = idx as i32 % self.width;
+            let y = idx as i32 / self.width;
+            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)].iter() {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < 0 || nx >= self.width || ny < 0 || ny >= self.height { continue; }
+                let next = self.xy_idx(nx, ny);
+                if !reached[next] && self.tiles[next].is_passable() {
+                    reached[next] = true;
+                    open.push(next);
+                }
             }
         }
 
-        map
+        let stairs_reached = self.tiles.iter().enumerate()
+            .all(|(idx, tile)| *tile != TileType::DownStairs || reached[idx]);
+        let rooms_reached = self.rooms.iter().enumerate()
+            .filter(|(i, _)| *i != skip)
+            .all(|(_, room)| {
+                (room.y1 + 1 ..= room.y2).any(|y| (room.x1 + 1 ..= room.x2).any(|x| reached[self.xy_idx(x, y)]))
+            });
+        stairs_reached && rooms_reached
+    }
+
+    /// Opens a door, letting creatures and sight through
+    pub fn open_door(&mut self, idx: usize) {
+        self.tiles[idx] = TileType::OpenDoor;
+        self.blocked[idx] = false;
+    }
+
+    /// Returns true if the tile is lit, which is the case inside rooms but not in corridors
+    pub fn is_lit(&self, x: i32, y: i32) -> bool {
+        self.rooms.iter().any(|room| x > room.x1 && x <= room.x2 && y > room.y1 && y <= room.y2)
+    }
+
+    /// Returns true if the tile lies within an elite room
+    pub fn in_elite_room(&self, x: i32, y: i32) -> bool {
+        self.elite_rooms.iter().any(|room| x > room.x1 && x <= room.x2 && y > room.y1 && y <= room.y2)
+    }
+
+    /// Tiles a shot from `from` towards `to` passes through, ending where it stops. Walls and shut
+    /// doors stop the shot short of themselves; projectiles also stop on the first blocking creature.
+    /// The shot reaches its target only if the last tile is `to`.
+    pub fn line_of_fire(&self, from: Point, to: Point, projectile: bool) -> Vec<Point> {
+        let mut points = line(from.x, from.y, to.x, to.y);
+        if points.first() != Some(&(from.x, from.y)) {
+            points.reverse();
+        }
+
+        let mut path = Vec::new();
+        for (x, y) in points.into_iter().skip(1) {
+            let idx = self.xy_idx(x, y);
+            if self.tiles[idx].is_solid() { break; }
+            path.push(Point::new(x, y));
+            if projectile && self.blocked[idx] { break; }
+        }
+        path
+    }
+
+    /// Returns true if a shot from `from` reaches `to` without hitting a wall or, if blocking, a creature
+    pub fn has_line_of_fire(&self, from: Point, to: Point, projectile: bool) -> bool {
+        from == to || self.line_of_fire(from, to, projectile).last() == Some(&to)
     }
 
     fn is_exit_valid(&self, x: i32, y: i32) -> bool {
         if x < 1 || x > self.width - 1 || y < 1 || y > self.height - 1 { return false; }
         let idx = self.xy_idx(x, y);
-        !self.blocked[idx]
+        !self.blocked[idx] || self.tiles[idx] == TileType::ClosedDoor
+    }
+
+    /// Cost of stepping onto a tile relative to open floor, which monsters use to route around hazards
+    pub fn movement_cost(&self, idx: usize) -> f32 {
+        let trap_cost = if self.traps.get(&idx).map_or(false, |t| t.revealed) { TRAP_AVOID_COST } else { 0.0 };
+        self.tiles[idx].movement_cost() + trap_cost
+    }
+
+    // Cost of stepping onto a tile, scaling the base cost of an orthogonal or diagonal step
+    fn step_cost(&self, idx: usize, base: f32) -> f32 {
+        base * self.movement_cost(idx)
     }
 }
 
 impl BaseMap for Map {
     fn is_opaque(&self, idx:usize) -> bool {
-        self.tiles[idx as usize] == TileType::Wall
+        matches!(self.tiles[idx], TileType::Wall | TileType::TallGrass | TileType::ClosedDoor | TileType::LockedDoor)
+            || self.tile_effects.get(&idx).map_or(false, |e| e.effect == TileEffectType::Smoke)
     }
 
+    // Straight line distance, which never overestimates since no tile costs less than floor
     fn get_pathing_distance(&self, idx1: usize, idx2: usize) -> f32 {
         let w = self.width as usize;
         let p1 = Point::new(idx1 % w, idx1 / w);
@@ -196,15 +498,15 @@ impl BaseMap for Map {
         let y = idx as i32 / self.width;
         let w = self.width as usize;
 
-        if self.is_exit_valid(x - 1, y) { exits.push((idx - 1, 1.0)) };
-        if self.is_exit_valid(x + 1, y) { exits.push((idx + 1, 1.0)) };
-        if self.is_exit_valid(x, y - 1) { exits.push((idx - w, 1.0)) };
-        if self.is_exit_valid(x, y + 1) { exits.push((idx + w, 1.0)) };
+        if self.is_exit_valid(x - 1, y) { exits.push((idx - 1, self.step_cost(idx - 1, 1.0))) };
+        if self.is_exit_valid(x + 1, y) { exits.push((idx + 1, self.step_cost(idx + 1, 1.0))) };
+        if self.is_exit_valid(x, y - 1) { exits.push((idx - w, self.step_cost(idx - w, 1.0))) };
+        if self.is_exit_valid(x, y + 1) { exits.push((idx + w, self.step_cost(idx + w, 1.0))) };
 
-        if self.is_exit_valid(x - 1, y - 1) { exits.push((idx - w - 1, 1.45)); }
-        if self.is_exit_valid(x + 1, y - 1) { exits.push((idx - w + 1, 1.45)); }
-        if self.is_exit_valid(x - 1, y + 1) { exits.push((idx + w - 1, 1.45)); }
-        if self.is_exit_valid(x + 1, y + 1) { exits.push((idx + w + 1, 1.45)); }
+        if self.is_exit_valid(x - 1, y - 1) { exits.push((idx - w - 1, self.step_cost(idx - w - 1, 1.45))); }
+        if self.is_exit_valid(x + 1, y - 1) { exits.push((idx - w + 1, self.step_cost(idx - w + 1, 1.45))); }
+        if self.is_exit_valid(x - 1, y + 1) { exits.push((idx + w - 1, self.step_cost(idx + w - 1, 1.45))); }
+        if self.is_exit_valid(x + 1, y + 1) { exits.push((idx + w + 1, self.step_cost(idx + w + 1, 1.45))); }
 
         exits
     }
@@ -216,6 +518,15 @@ impl Algorithm2D for Map {
     }
 }
 
+/// Returns true if `to` is within `range` of `from`. Melee range is strict adjacency, including diagonals.
+pub fn in_range(from: Point, to: Point, range: i32) -> bool {
+    if range <= 1 {
+        i32::max((from.x - to.x).abs(), (from.y - to.y).abs()) <= range
+    } else {
+        rltk::DistanceAlg::Pythagoras.distance2d(from, to) <= range as f32
+    }
+}
+
 pub fn draw_map(ecs: &World, ctx: &mut Rltk) {
     let map = ecs.fetch::<Map>();
 
@@ -223,7 +534,7 @@ pub fn draw_map(ecs: &World, ctx: &mut Rltk) {
     let mut x = 0;
     for (idx, tile) in map.tiles.iter().enumerate() {
         if map.revealed_tiles[idx] {
-            let glyph;
+            let mut glyph;
             let mut fg;
             match tile {
                 TileType::Floor => {
@@ -238,9 +549,79 @@ pub fn draw_map(ecs: &World, ctx: &mut Rltk) {
                     glyph = rltk::to_cp437('>');
                     fg = RGB::from_f32(0.0, 1.0, 1.0);
                 }
+                TileType::ShallowWater => {
+                    glyph = rltk::to_cp437('~');
+                    fg = RGB::from_f32(0.3, 0.6, 1.0);
+                }
+                TileType::DeepWater => {
+                    glyph = rltk::to_cp437('≈');
+                    fg = RGB::from_f32(0.1, 0.2, 0.9);
+                }
+                TileType::TallGrass => {
+                    glyph = rltk::to_cp437('"');
+                    fg = RGB::from_f32(0.2, 0.7, 0.1);
+                }
+                TileType::Lava => {
+                    glyph = rltk::to_cp437('▒');
+                    fg = RGB::from_f32(1.0, 0.35, 0.0);
+                }
+                TileType::Rubble => {
+                    glyph = rltk::to_cp437(':');
+                    fg = RGB::from_f32(0.55, 0.5, 0.45);
+                }
+                TileType::ClosedDoor => {
+                    glyph = rltk::to_cp437('+');
+                    fg = RGB::from_f32(0.6, 0.4, 0.1);
+                }
+                TileType::OpenDoor => {
+                    glyph = rltk::to_cp437('\'');
+                    fg = RGB::from_f32(0.6, 0.4, 0.1);
+                }
+                TileType::LockedDoor => {
+                    glyph = rltk::to_cp437('+');
+                    fg = RGB::named(rltk::GOLD);
+                }
+            }
+            let mut bg = RGB::from_f32(0., 0., 0.);
+            if map.in_elite_room(x, y) { bg = RGB::from_f32(0.25, 0.0, 0.05); }
+            // Traps stay hidden until found
+            if let Some(trap) = map.traps.get(&idx).filter(|t| t.revealed) {
+                glyph = rltk::to_cp437('^');
+                fg = match trap.trap {
+                    TrapType::Spike => RGB::named(rltk::RED),
+                    TrapType::Alarm => RGB::named(rltk::YELLOW),
+                    TrapType::Teleport => RGB::named(rltk::MAGENTA),
+                    TrapType::Curse => RGB::named(rltk::PURPLE),
+                };
+            }
+
+            // Tile effects are only drawn where the player can currently see them
+            if let (true, Some(effect)) = (map.visible_tiles[idx], map.tile_effects.get(&idx)) {
+                match effect.effect {
+                    TileEffectType::Caltrops => {
+                        glyph = rltk::to_cp437('%');
+                        fg = RGB::named(rltk::SILVER);
+                    }
+                    TileEffectType::Smoke => {
+                        glyph = rltk::to_cp437('░');
+                        fg = RGB::named(rltk::GRAY);
+                    }
+                    TileEffectType::PoisonGas => {
+                        glyph = rltk::to_cp437('░');
+                        fg = RGB::named(rltk::CHARTREUSE);
+                    }
+                    TileEffectType::Oil => {
+                        glyph = rltk::to_cp437('~');
+                        fg = RGB::named(rltk::SADDLEBROWN);
+                        bg = RGB::from_f32(0.1, 0.06, 0.0);
+                    }
+                }
+            }
+            if !map.visible_tiles[idx] {
+                fg = fg.to_greyscale();
+                bg = bg.to_greyscale();
             }
-            if !map.visible_tiles[idx] { fg = fg.to_greyscale() }
-            ctx.set(x, y, fg, RGB::from_f32(0., 0., 0.), glyph);
+            ctx.set(x, y, fg, bg, glyph);
         }
 
         x += 1;
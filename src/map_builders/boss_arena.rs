@@ -0,0 +1,55 @@
+use rltk::RandomNumberGenerator;
+
+use super::MapBuilder;
+use super::super::{map::{Map, TileType}, util::Rect};
+
+/// Builds an antechamber leading into a single large arena with its corners cut away and
+/// pillars for cover around the boss
+pub struct BossArenaBuilder {}
+
+impl MapBuilder for BossArenaBuilder {
+    fn build(&self, map: &mut Map, _rng: &mut RandomNumberGenerator) {
+        let antechamber = Rect::new(4, map.height / 2 - 3, 6, 6);
+        let arena = Rect::new(map.width / 2 - 15, 2, 30, map.height - 5);
+        map.apply_room(&antechamber);
+        map.apply_room(&arena);
+        apply_arena_features(map, &arena);
+
+        let (ante_x, ante_y) = antechamber.center();
+        let (arena_x, arena_y) = arena.center();
+        map.apply_euclidean_corridor(ante_x, ante_y, arena_x, arena_y);
+
+        map.rooms.push(antechamber);
+        map.rooms.push(arena);
+    }
+
+    // The arena is laid out by hand, and its down stairs are placed where the boss falls
+    fn post_process(&self, _map: &mut Map, _rng: &mut RandomNumberGenerator) {}
+}
+
+fn apply_arena_features(map: &mut Map, arena: &Rect) {
+    const CORNER_CUT: i32 = 5;
+    const PILLAR_OFFSET_X: i32 = 8;
+    const PILLAR_OFFSET_Y: i32 = 6;
+
+    for y in arena.y1 + 1 ..= arena.y2 {
+        for x in arena.x1 + 1 ..= arena.x2 {
+            let dx = i32::min(x - arena.x1 - 1, arena.x2 - x);
+            let dy = i32::min(y - arena.y1 - 1, arena.y2 - y);
+            if dx + dy < CORNER_CUT {
+                let idx = map.xy_idx(x, y);
+                map.tiles[idx] = TileType::Wall;
+            }
+        }
+    }
+
+    let (center_x, center_y) = arena.center();
+    for (px, py) in [(-1, -1), (1, -1), (-1, 1), (1, 1)].iter() {
+        let pillar_x = center_x + px * PILLAR_OFFSET_X;
+        let pillar_y = center_y + py * PILLAR_OFFSET_Y;
+        for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)].iter() {
+            let idx = map.xy_idx(pillar_x + dx, pillar_y + dy);
+            map.tiles[idx] = TileType::Wall;
+        }
+    }
+}
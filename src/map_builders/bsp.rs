@@ -0,0 +1,69 @@
+use rltk::RandomNumberGenerator;
+
+use super::MapBuilder;
+use super::super::{map::Map, util::Rect};
+
+// Leaves are never split below this width or height, which keeps room for a room and its walls
+const MIN_LEAF: i32 = 10;
+const MIN_ROOM: i32 = 4;
+const MAX_ROOM: i32 = 12;
+
+/// Splits the map in two again and again, puts a room in every leaf, then joins the rooms of
+/// neighbouring leaves. Rooms spread evenly over the map and corridors stay short.
+pub struct BspBuilder {}
+
+impl MapBuilder for BspBuilder {
+    fn build(&self, map: &mut Map, rng: &mut RandomNumberGenerator) {
+        let mut leaves = Vec::new();
+        split(Rect::new(0, 0, map.width - 1, map.height - 1), rng, &mut leaves);
+
+        for leaf in leaves.iter() {
+            let leaf_w = leaf.x2 - leaf.x1;
+            let leaf_h = leaf.y2 - leaf.y1;
+            let w = rng.range(MIN_ROOM, i32::min(MAX_ROOM, leaf_w - 2) + 1);
+            let h = rng.range(MIN_ROOM, i32::min(MAX_ROOM, leaf_h - 2) + 1);
+            let x = leaf.x1 + rng.range(0, leaf_w - w);
+            let y = leaf.y1 + rng.range(0, leaf_h - h);
+            let room = Rect::new(x, y, w, h);
+            map.apply_room(&room);
+
+            // Leaves come out in tree order, so each room is joined to its neighbour
+            if let Some(prev) = map.rooms.last() {
+                let (new_x, new_y) = room.center();
+                let (prev_x, prev_y) = prev.center();
+                let right = rng.range(0, 2) == 0;
+                map.apply_manhattan_corridor(new_x, new_y, prev_x, prev_y, right);
+            }
+            map.rooms.push(room);
+        }
+    }
+
+    fn has_rooms(&self) -> bool { true }
+}
+
+// Splits a rectangle along its longer side until it is too small to split
+fn split(rect: Rect, rng: &mut RandomNumberGenerator, leaves: &mut Vec<Rect>) {
+    let w = rect.x2 - rect.x1;
+    let h = rect.y2 - rect.y1;
+    let can_split_x = w >= MIN_LEAF * 2;
+    let can_split_y = h >= MIN_LEAF * 2;
+    if !can_split_x && !can_split_y {
+        leaves.push(rect);
+        return;
+    }
+
+    let vertical = match (can_split_x, can_split_y) {
+        (true, false) => true,
+        (false, true) => false,
+        _ => if w * 4 > h * 5 { true } else if h * 4 > w * 5 { false } else { rng.range(0, 2) == 0 },
+    };
+    if vertical {
+        let at = rng.range(MIN_LEAF, w - MIN_LEAF + 1);
+        split(Rect::new(rect.x1, rect.y1, at, h), rng, leaves);
+        split(Rect::new(rect.x1 + at, rect.y1, w - at, h), rng, leaves);
+    } else {
+        let at = rng.range(MIN_LEAF, h - MIN_LEAF + 1);
+        split(Rect::new(rect.x1, rect.y1, w, at), rng, leaves);
+        split(Rect::new(rect.x1, rect.y1 + at, w, h - at), rng, leaves);
+    }
+}
@@ -0,0 +1,43 @@
+use rltk::RandomNumberGenerator;
+
+use super::MapBuilder;
+use super::super::map::{Map, TileType};
+
+const ITERATIONS: i32 = 12;
+// Chance out of 100 for each tile to start open
+const OPEN_CHANCE: i32 = 55;
+
+/// Fills the map with noise and smooths it into caves, turning tiles with many walls
+/// around them into walls and tiles with few into floor
+pub struct CellularAutomataBuilder {}
+
+impl MapBuilder for CellularAutomataBuilder {
+    fn build(&self, map: &mut Map, rng: &mut RandomNumberGenerator) {
+        for y in 1 .. map.height - 1 {
+            for x in 1 .. map.width - 1 {
+                let idx = map.xy_idx(x, y);
+                map.tiles[idx] = if rng.roll_dice(1, 100) <= OPEN_CHANCE { TileType::Floor } else { TileType::Wall };
+            }
+        }
+
+        for _ in 0 .. ITERATIONS {
+            let mut next_tiles = map.tiles.clone();
+            for y in 1 .. map.height - 1 {
+                for x in 1 .. map.width - 1 {
+                    let mut walls = 0;
+                    for dy in -1 ..= 1 {
+                        for dx in -1 ..= 1 {
+                            if (dx != 0 || dy != 0) && map.tiles[map.xy_idx(x + dx, y + dy)] == TileType::Wall {
+                                walls += 1;
+                            }
+                        }
+                    }
+
+                    let idx = map.xy_idx(x, y);
+                    next_tiles[idx] = if walls > 4 || walls == 0 { TileType::Wall } else { TileType::Floor };
+                }
+            }
+            map.tiles = next_tiles;
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use rltk::RandomNumberGenerator;
+
+use super::MapBuilder;
+use super::super::map::{Map, TileType};
+
+// Share of the map to dig out before stopping
+const OPEN_PERCENT: usize = 45;
+const DIGGER_STEPS: i32 = 400;
+
+/// Sends diggers stumbling about from open tiles until enough of the map is dug out,
+/// leaving winding tunnels that always connect back to where digging began
+pub struct DrunkardsWalkBuilder {}
+
+impl MapBuilder for DrunkardsWalkBuilder {
+    fn build(&self, map: &mut Map, rng: &mut RandomNumberGenerator) {
+        let center = map.xy_idx(map.width / 2, map.height / 2);
+        map.tiles[center] = TileType::Floor;
+
+        let goal = map.tiles.len() * OPEN_PERCENT / 100;
+        let mut open: Vec<usize> = vec![center];
+        while open.len() < goal {
+            // Each digger sets off from somewhere already dug, so the tunnels stay connected
+            let start = open[rng.range(0, open.len() as i32) as usize];
+            let mut x = start as i32 % map.width;
+            let mut y = start as i32 / map.width;
+            for _ in 0 .. DIGGER_STEPS {
+                let idx = map.xy_idx(x, y);
+                if map.tiles[idx] == TileType::Wall {
+                    map.tiles[idx] = TileType::Floor;
+                    open.push(idx);
+                }
+                match rng.roll_dice(1, 4) {
+                    1 => if x > 2 { x -= 1 },
+                    2 => if x < map.width - 3 { x += 1 },
+                    3 => if y > 2 { y -= 1 },
+                    _ => if y < map.height - 3 { y += 1 },
+                }
+            }
+        }
+    }
+}
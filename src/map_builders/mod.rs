@@ -0,0 +1,185 @@
+use rltk::{Point, RandomNumberGenerator};
+
+use super::{act, flowfield::FlowField, util::Rect};
+use super::map::{Map, TileType, MAPWIDTH, MAPHEIGHT, build_map};
+
+mod boss_arena;
+mod bsp;
+mod cellular_automata;
+mod drunkard;
+mod rooms_and_corridors;
+
+pub use boss_arena::BossArenaBuilder;
+pub use bsp::BspBuilder;
+pub use cellular_automata::CellularAutomataBuilder;
+pub use drunkard::DrunkardsWalkBuilder;
+pub use rooms_and_corridors::RoomsAndCorridorsBuilder;
+
+/// Lays out the walls and floor of a level, leaving the finishing touches every level shares to `post_process`
+pub trait MapBuilder {
+    /// Carves the layout out of a map filled with walls. Builders that make rooms put the
+    /// player's starting room first.
+    fn build(&self, map: &mut Map, rng: &mut RandomNumberGenerator);
+
+    /// Layouts made of rooms joined by corridors also get ponds and doors
+    fn has_rooms(&self) -> bool { false }
+
+    /// Makes the level playable once built: cuts off unreachable areas, picks the player's start,
+    /// marks out areas to spawn in for layouts without rooms, puts the stairs as far from the
+    /// start as possible, then dresses the level with terrain, doors and traps
+    fn post_process(&self, map: &mut Map, rng: &mut RandomNumberGenerator) {
+        cull_unreachable(map);
+        let start = choose_start(map);
+        if !self.has_rooms() {
+            map.rooms = spawn_areas(map, start, rng);
+        }
+        place_stairs(map, start);
+
+        map.apply_terrain_features(rng, self.has_rooms());
+        if self.has_rooms() {
+            map.apply_doors();
+        }
+        map.apply_traps(rng);
+    }
+}
+
+/// Picks the generator for a floor. Each act favours its own kind of layout, from the
+/// dungeon halls of Exordium to the caves of the Beyond, and every act ends in a boss arena.
+pub fn builder_for_floor(map_depth: i32, rng: &mut RandomNumberGenerator) -> Box<dyn MapBuilder> {
+    if act::is_boss_floor(map_depth) {
+        return Box::new(BossArenaBuilder{});
+    }
+
+    match act::Act::from_floor(map_depth) {
+        act::Act::Exordium => match rng.roll_dice(1, 2) {
+            1 => Box::new(RoomsAndCorridorsBuilder{}),
+            _ => Box::new(BspBuilder{}),
+        },
+        act::Act::City => match rng.roll_dice(1, 3) {
+            1 => Box::new(RoomsAndCorridorsBuilder{}),
+            2 => Box::new(BspBuilder{}),
+            _ => Box::new(DrunkardsWalkBuilder{}),
+        },
+        act::Act::Beyond => match rng.roll_dice(1, 2) {
+            1 => Box::new(CellularAutomataBuilder{}),
+            _ => Box::new(DrunkardsWalkBuilder{}),
+        },
+    }
+}
+
+/// Builds a floor with the given generator
+pub fn build_floor(builder: &dyn MapBuilder, map_depth: i32, rng: &mut RandomNumberGenerator) -> Map {
+    let mut map = build_map(MAPWIDTH as i32, MAPHEIGHT as i32, TileType::Wall, map_depth);
+    builder.build(&mut map, rng);
+    builder.post_process(&mut map, rng);
+    map.populate_blocked();
+    map
+}
+
+const NEIGHBOURS: [(i32, i32); 8] = [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+// Walls off every floor tile outside the largest connected region
+fn cull_unreachable(map: &mut Map) {
+    let mut region = vec![0; map.tiles.len()];
+    let mut largest = (0, 0);
+    let mut next_region = 0;
+    for start in 0 .. map.tiles.len() {
+        if region[start] != 0 || map.tiles[start] == TileType::Wall { continue; }
+
+        next_region += 1;
+        region[start] = next_region;
+        let mut size = 0;
+        let mut open = vec![start];
+        while let Some(idx) = open.pop() {
+            size += 1;
+            let x = idx as i32 % map.width;
+            let y = idx as i32 / map.width;
+            for (dx, dy) in NEIGHBOURS.iter() {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < 0 || nx >= map.width || ny < 0 || ny >= map.height { continue; }
+                let next = map.xy_idx(nx, ny);
+                if region[next] == 0 && map.tiles[next] != TileType::Wall {
+                    region[next] = next_region;
+                    open.push(next);
+                }
+            }
+        }
+        if size > largest.1 {
+            largest = (next_region, size);
+        }
+    }
+
+    for (idx, tile) in map.tiles.iter_mut().enumerate() {
+        if region[idx] != largest.0 {
+            *tile = TileType::Wall;
+        }
+    }
+}
+
+// The centre of the first room, or for layouts without rooms the open tile closest to the
+// middle of the left edge so that the stairs end up across the map
+fn choose_start(map: &Map) -> Point {
+    if let Some(room) = map.rooms.first() {
+        let (x, y) = room.center();
+        return Point::new(x, y);
+    }
+
+    let anchor = Point::new(map.width / 8, map.height / 2);
+    let idx = (0 .. map.tiles.len())
+        .filter(|idx| map.tiles[*idx] == TileType::Floor)
+        .min_by_key(|idx| {
+            let point = Point::new(*idx as i32 % map.width, *idx as i32 / map.width);
+            (rltk::DistanceAlg::PythagorasSquared.distance2d(anchor, point) * 100.0) as i32
+        })
+        .expect("Map has no open tiles");
+    Point::new(idx as i32 % map.width, idx as i32 / map.width)
+}
+
+// Areas spawns are placed in, standing in for rooms on layouts that do not have them.
+// The first area is centred on the player's start, like the starting room.
+fn spawn_areas(map: &Map, start: Point, rng: &mut RandomNumberGenerator) -> Vec<Rect> {
+    const AREA_SIZE: i32 = 9;
+    const MIN_OPEN_TILES: usize = 20;
+    const MAX_AREAS: usize = 12;
+    const START_AREA_SIZE: i32 = 6;
+
+    // Kept inside the map's border when the player starts near an edge
+    let start_x = (start.x - START_AREA_SIZE / 2).max(0).min(map.width - START_AREA_SIZE - 1);
+    let start_y = (start.y - START_AREA_SIZE / 2).max(0).min(map.height - START_AREA_SIZE - 1);
+    let start_area = Rect::new(start_x, start_y, START_AREA_SIZE, START_AREA_SIZE);
+
+    let mut candidates = Vec::new();
+    let mut y = 0;
+    while y + AREA_SIZE < map.height - 1 {
+        let mut x = 0;
+        while x + AREA_SIZE < map.width - 1 {
+            let area = Rect::new(x, y, AREA_SIZE, AREA_SIZE);
+            let open_tiles = (area.y1 + 1 ..= area.y2)
+                .flat_map(|ty| (area.x1 + 1 ..= area.x2).map(move |tx| (tx, ty)))
+                .filter(|(tx, ty)| map.tiles[map.xy_idx(*tx, *ty)] == TileType::Floor)
+                .count();
+            if !area.intersect(&start_area) && open_tiles >= MIN_OPEN_TILES {
+                candidates.push(area);
+            }
+            x += AREA_SIZE;
+        }
+        y += AREA_SIZE;
+    }
+
+    let mut areas = vec![start_area];
+    while areas.len() <= MAX_AREAS && !candidates.is_empty() {
+        let pick = rng.range(0, candidates.len() as i32) as usize;
+        areas.push(candidates.remove(pick));
+    }
+    areas
+}
+
+// Puts the down stairs on the open tile furthest from the player's start
+fn place_stairs(map: &mut Map, start: Point) {
+    let flow = FlowField::new(map, start);
+    let stairs_idx = (0 .. map.tiles.len())
+        .filter(|idx| map.tiles[*idx] == TileType::Floor && flow.approach[*idx] != i32::MAX)
+        .max_by_key(|idx| flow.approach[*idx])
+        .expect("Map has no reachable tiles");
+    map.tiles[stairs_idx] = TileType::DownStairs;
+}
@@ -0,0 +1,46 @@
+use rltk::RandomNumberGenerator;
+
+use super::MapBuilder;
+use super::super::{map::Map, util::Rect};
+
+/// Scatters rectangular rooms over the map, joining each to the last with an L-shaped corridor
+pub struct RoomsAndCorridorsBuilder {}
+
+impl MapBuilder for RoomsAndCorridorsBuilder {
+    fn build(&self, map: &mut Map, rng: &mut RandomNumberGenerator) {
+        const MAX_ROOMS: i32 = 30;
+        const MIN_SIZE: i32 = 6;
+        const MAX_SIZE:i32 = 10;
+
+        for _ in 0 .. MAX_ROOMS {
+            let w = rng.range(MIN_SIZE, MAX_SIZE);
+            let h = rng.range(MIN_SIZE, MAX_SIZE);
+            let x = rng.roll_dice(1, map.width - w - 1) - 1;
+            let y = rng.roll_dice(1, map.height - h - 1) - 1;
+            let new_room = Rect::new(x, y, w, h);
+            let mut ok = true;
+            for other_room in map.rooms.iter() {
+                if new_room.intersect(other_room) { ok = false }
+            }
+    
+            if ok {
+                map.apply_room(&new_room);
+    
+                if !map.rooms.is_empty() {
+                    let (new_x, new_y) = new_room.center();
+                    let (prev_x, prev_y) = map.rooms[map.rooms.len()-1].center();
+    
+                    if rng.range(0, 2) == 0 {
+                        map.apply_manhattan_corridor(new_x, new_y, prev_x, prev_y, true);
+                    } else {
+                        map.apply_manhattan_corridor(new_x, new_y, prev_x, prev_y, false);
+                    }
+                }
+    
+                map.rooms.push(new_room);
+            }
+        }
+    }
+
+    fn has_rooms(&self) -> bool { true }
+}
@@ -1,7 +1,7 @@
 use specs::prelude::*;
 use rltk::{Rltk, RGB, VirtualKeyCode};
 
-use super::{RunState, saveload};
+use super::{RunState, saveload, creature, character::{self, Character, CHARACTERS}};
 
 #[derive(PartialEq, Copy, Clone, Debug)]
 pub enum MainMenuSelection {
@@ -16,6 +16,13 @@ pub enum MainMenuResult {
     Selected{ selected: MainMenuSelection },
 }
 
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum CharacterSelectResult {
+    NoSelection{ selected: Character },
+    Selected{ selected: Character },
+    Cancel,
+}
+
 pub fn main_menu(ecs: &mut World, ctx: &mut Rltk) -> MainMenuResult {
     let save_exists = saveload::save_exists();
     let runstate = ecs.fetch::<RunState>();
@@ -88,4 +95,72 @@ pub fn main_menu(ecs: &mut World, ctx: &mut Rltk) -> MainMenuResult {
     }
 
     MainMenuResult::NoSelection { selected: MainMenuSelection::NewGame }
+}
+
+pub fn character_select(ctx: &mut Rltk, selection: Character) -> CharacterSelectResult {
+    let x = 5;
+    let color_selected = RGB::named(rltk::MAGENTA);
+    let color_unselected = RGB::named(rltk::WHITE);
+
+    let mut y = super::WINDOWHEIGHT - 12;
+    ctx.print_color(x, y, RGB::named(rltk::YELLOW), RGB::named(rltk::BLACK), "Choose your character");
+    y += 2;
+
+    // Draw character list
+    let details_y = y;
+    for character in CHARACTERS.iter() {
+        let mut selected = color_unselected;
+        if *character == selection { selected = color_selected; }
+        ctx.print_color(x, y, selected, RGB::named(rltk::BLACK), character.to_name());
+        y += 2;
+    }
+
+    // Draw details of the highlighted character
+    let details_x = x + 15;
+    let relic = selection.starter_relic();
+    ctx.print_color(details_x, details_y, selection.color(), RGB::named(rltk::BLACK), selection.to_name());
+    ctx.print_color(details_x, details_y + 1, RGB::named(rltk::RED), RGB::named(rltk::BLACK),
+        format!("HP: {}", selection.max_hp()));
+    ctx.print_color(details_x, details_y + 2, RGB::named(rltk::YELLOW), RGB::named(rltk::BLACK),
+        format!("Energy: {}", selection.max_energy()));
+    ctx.print_color(details_x, details_y + 4, RGB::named(rltk::ORANGE), RGB::named(rltk::BLACK), relic.to_name());
+    ctx.print_color(details_x, details_y + 5, RGB::named(rltk::WHITE), RGB::named(rltk::BLACK), relic.description());
+
+    match ctx.key {
+        None => CharacterSelectResult::NoSelection{ selected: selection },
+        Some(key) => {
+            match key {
+                VirtualKeyCode::Escape => CharacterSelectResult::Cancel,
+                VirtualKeyCode::Up | VirtualKeyCode::W => CharacterSelectResult::NoSelection{ selected: selection.prev() },
+                VirtualKeyCode::Down | VirtualKeyCode::S => CharacterSelectResult::NoSelection{ selected: selection.next() },
+                VirtualKeyCode::Return | VirtualKeyCode::Space => CharacterSelectResult::Selected{ selected: selection },
+                _ => CharacterSelectResult::NoSelection{ selected: selection },
+            }
+        }
+    }
+}
+
+/// Draws the victory screen, returning true once the player dismisses it
+pub fn victory(ecs: &World, ctx: &mut Rltk) -> bool {
+    let x = 5;
+    let mut y = super::WINDOWHEIGHT - 12;
+    let character = character::player_character(ecs);
+
+    ctx.print_color(x, y, RGB::named(rltk::YELLOW), RGB::named(rltk::BLACK), "Victory!");
+    y += 2;
+    ctx.print_color(x, y, character.color(), RGB::named(rltk::BLACK),
+        format!("The {} has conquered the Spire.", character.to_name()));
+    y += 1;
+    let player_entity = ecs.fetch::<Entity>();
+    if let Some(stats) = ecs.read_storage::<creature::CombatStats>().get(*player_entity) {
+        ctx.print_color(x, y, RGB::named(rltk::RED), RGB::named(rltk::BLACK),
+            format!("HP remaining: {} / {}", stats.hp, stats.max_hp));
+    }
+    y += 2;
+    ctx.print_color(x, y, RGB::named(rltk::GREY), RGB::named(rltk::BLACK), "Press Enter to return to the main menu.");
+
+    match ctx.key {
+        Some(VirtualKeyCode::Return) | Some(VirtualKeyCode::Escape) => true,
+        _ => false,
+    }
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 use specs::prelude::*;
 use serde::{Serialize, Deserialize};
+use rltk::Point;
 
 use super::super::{
-    Name, creature, effects, item, status,
+    Name, creature, effects, item, status, map::in_range, passive::Passive,
 };
 
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
@@ -14,15 +15,71 @@ pub enum Attacks {
     AttackAndApplyWeak{ name: String, range: i32, amount:i32, turns: i32 },
     AttackAndApplyVulnerable{ name: String, range: i32, amount:i32, turns: i32 },
     ApplyFrail{ name: String, range: i32, turns: i32 },
+    ApplyEntangled{ name: String, range: i32, turns: i32 },
+    ApplyRooted{ name: String, range: i32, turns: i32 },
+    ApplySlowed{ name: String, range: i32, turns: i32 },
     BuffStrength{ name: String, range: i32, amount: i32 },
     BlockAndBuffStrength{ name: String, range: i32, block_amount: i32, buff_amount: i32 },
     AttackAndGiveCard{ name: String, range: i32, amount: i32, card: effects::GainableCard, number: i32 },
+    GiveCard{ name: String, range: i32, card: effects::GainableCard, number: i32 },
+    DebuffStrengthAndDexterity{ name: String, range: i32, strength_amount: i32, dexterity_amount: i32 },
+    Divider{ name: String, range: i32, hits: i32 },
+    GainPassive{ name: String, range: i32, passive: Passive },
+    AttackAndStealGold{ name: String, range: i32, amount: i32, gold: i32 },
+    BlockAndFlee{ name: String, range: i32, amount: i32 },
+    BlockAlly{ name: String, range: i32, amount: i32 },
+    BuffAllies{ name: String, range: i32, amount: i32 },
+    AttackAndPush{ name: String, range: i32, amount: i32, distance: i32, collision_damage: i32 },
+    Pull{ name: String, range: i32, distance: i32 },
+    Swap{ name: String, range: i32 },
     Pass{ name: String, range: i32 },
 }
 
+// Allies of the caster that it can see within range, given every monster as (entity, position, hp)
+pub fn allies_in_range(caster: Entity, origin: Point, range: i32, visible: &[Point],
+    monsters: &[(Entity, Point, i32)]) -> Vec<(Entity, Point, i32)> {
+    monsters.iter()
+        .filter(|(ent, pos, hp)| *ent != caster && *hp > 0 && visible.contains(pos)
+            && in_range(origin, *pos, range))
+        .cloned()
+        .collect()
+}
+
+// The monsters a caster will support, given every monster as (entity, position, hp).
+// Gremlins only support other gremlins, while other monsters support any monster
+pub fn supported_allies(caster: Entity, monsters: &[(Entity, Point, i32)], gremlins: &ReadStorage<creature::Gremlin>) -> Vec<(Entity, Point, i32)> {
+    let caster_is_gremlin = gremlins.get(caster).is_some();
+    monsters.iter()
+        .filter(|(ent, _, _)| !caster_is_gremlin || gremlins.get(*ent).is_some())
+        .cloned()
+        .collect()
+}
+
+// The ally in range with the least health
+pub fn weakest_ally_in_range(caster: Entity, origin: Point, range: i32, visible: &[Point],
+    monsters: &[(Entity, Point, i32)]) -> Option<(Entity, Point, i32)> {
+    allies_in_range(caster, origin, range, visible, monsters).into_iter()
+        .min_by_key(|(_, _, hp)| *hp)
+}
+
+// Divider deals damage based on the player's current health when it is telegraphed
+fn divider_damage(ecs: &World, hits: i32) -> i32 {
+    let player_entity = ecs.fetch::<Entity>();
+    let combat_stats = ecs.read_storage::<creature::CombatStats>();
+    match combat_stats.get(*player_entity) {
+        Some(stats) => (stats.hp / 12 + 1) * hits,
+        None => hits,
+    }
+}
+
 impl Attacks {
     // Creates and returns the associated atttack Entity
     pub fn to_attack(self, ecs: &mut World) -> Entity {
+        let divider_amount = match &self {
+            Attacks::Divider{hits, ..} => divider_damage(ecs, *hits),
+            _ => 0,
+        };
+
         let mut attack = ecs.create_entity()
             .with(creature::Attack{});
     
@@ -65,6 +122,21 @@ impl Attacks {
                     .with(item::Targeted{ range })
                     .with(status::Frail{ turns });
             }
+            Attacks::ApplyEntangled{name, range, turns} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(status::Entangled{ turns });
+            }
+            Attacks::ApplyRooted{name, range, turns} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(status::Rooted{ turns });
+            }
+            Attacks::ApplySlowed{name, range, turns} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(status::Slowed{ turns, started: false });
+            }
             Attacks::BuffStrength{name, range, amount} => {
                 attack = attack.with(Name{ name: name.to_string() })
                     .with(item::Targeted{ range })
@@ -82,6 +154,68 @@ impl Attacks {
                     .with(effects::DealDamage{ amount })
                     .with(effects::GainCard{ card, number, to_hand: false })
             }
+            Attacks::GiveCard{name, range, card, number} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(effects::GainCard{ card, number, to_hand: false })
+            }
+            Attacks::DebuffStrengthAndDexterity{name, range, strength_amount, dexterity_amount} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(effects::DebuffStrength{ amount: strength_amount })
+                    .with(effects::DebuffDexterity{ amount: dexterity_amount })
+            }
+            Attacks::Divider{name, range, ..} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(effects::DealDamage{ amount: divider_amount })
+            }
+            Attacks::GainPassive{name, range, passive} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(effects::GainPassive{ passive })
+            }
+            Attacks::AttackAndStealGold{name, range, amount, gold} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(effects::DealDamage{ amount })
+                    .with(effects::StealGold{ amount: gold })
+            }
+            Attacks::BlockAndFlee{name, range, amount} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(effects::GainBlock{ amount })
+                    .with(effects::Flee{})
+            }
+            Attacks::BlockAlly{name, range, amount} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(item::TargetsAllies{})
+                    .with(effects::GrantBlock{ amount })
+            }
+            Attacks::BuffAllies{name, range, amount} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(item::TargetsAllies{})
+                    .with(item::AreaOfEffect{ radius: range, shape: item::AoeShape::Burst, friendly_fire: false })
+                    .with(effects::GrantStrength{ amount })
+            }
+            Attacks::AttackAndPush{name, range, amount, distance, collision_damage} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(effects::DealDamage{ amount })
+                    .with(effects::Push{ distance, collision_damage })
+            }
+            Attacks::Pull{name, range, distance} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(effects::Pull{ distance })
+            }
+            Attacks::Swap{name, range} => {
+                attack = attack.with(Name{ name: name.to_string() })
+                    .with(item::Targeted{ range })
+                    .with(effects::Swap{})
+            }
             Attacks::Pass{name, range} => {
                 attack = attack.with(Name{ name: name.to_string() })
                     .with(item::Targeted{ range })
@@ -1,5 +1,6 @@
 use specs::prelude::*;
 use rltk::RandomNumberGenerator;
+use serde::{Serialize, Deserialize};
 
 use super::mobs;
 
@@ -17,6 +18,28 @@ pub enum Encounters {
     Looter(i32),
     ExordiumThugs(i32),
     ExordiumWildlife(i32),
+    GremlinNob(i32),
+    Lagavulin(i32),
+    Sentries,
+    SlimeBoss,
+    TheGuardian,
+    Hexaghost,
+    Byrds(i32),
+    GremlinLeader(i32),
+    Chosen(i32),
+    ShelledParasite(i32),
+    SnakePlant(i32),
+    CenturionAndMystic(i32),
+    BookOfStabbing(i32),
+    Taskmaster(i32),
+    TheChamp,
+    Darklings(i32),
+    OrbWalker(i32),
+    Spikers(i32),
+    Repulsors(i32),
+    GiantHead(i32),
+    Nemesis(i32),
+    AwakenedOne,
 }
 
 impl Encounters {
@@ -145,8 +168,136 @@ impl Encounters {
                     }
                 }
             }
+            Encounters::GremlinNob(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::gremlin_nob(ecs, 0, 0));
+                }
+            }
+            Encounters::Lagavulin(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::lagavulin(ecs, 0, 0));
+                }
+            }
+            Encounters::Sentries => {
+                spawned.push(mobs::sentry(ecs, 0, 0, false));
+                spawned.push(mobs::sentry(ecs, 0, 0, true));
+                spawned.push(mobs::sentry(ecs, 0, 0, false));
+            }
+            Encounters::SlimeBoss => {
+                spawned.push(mobs::slime_boss(ecs, 0, 0));
+            }
+            Encounters::TheGuardian => {
+                spawned.push(mobs::the_guardian(ecs, 0, 0));
+            }
+            Encounters::Hexaghost => {
+                spawned.push(mobs::hexaghost(ecs, 0, 0));
+            }
+            Encounters::Byrds(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::byrd(ecs, 0, 0));
+                }
+            }
+            Encounters::GremlinLeader(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::gremlin_leader(ecs, 0, 0));
+                    spawned.extend(Encounters::GremlinGang(2).spawn(ecs));
+                }
+            }
+            Encounters::Chosen(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::chosen(ecs, 0, 0));
+                }
+            }
+            Encounters::ShelledParasite(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::shelled_parasite(ecs, 0, 0));
+                }
+            }
+            Encounters::SnakePlant(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::snake_plant(ecs, 0, 0));
+                }
+            }
+            Encounters::CenturionAndMystic(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::centurion(ecs, 0, 0));
+                    spawned.push(mobs::mystic(ecs, 0, 0));
+                }
+            }
+            Encounters::BookOfStabbing(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::book_of_stabbing(ecs, 0, 0));
+                }
+            }
+            Encounters::Taskmaster(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::taskmaster(ecs, 0, 0));
+                    spawned.push(mobs::red_slaver(ecs, 0, 0));
+                    spawned.push(mobs::blue_slaver(ecs, 0, 0));
+                }
+            }
+            Encounters::TheChamp => {
+                spawned.push(mobs::the_champ(ecs, 0, 0));
+            }
+            Encounters::Darklings(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::darkling(ecs, 0, 0));
+                }
+            }
+            Encounters::OrbWalker(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::orb_walker(ecs, 0, 0));
+                }
+            }
+            Encounters::Spikers(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::spiker(ecs, 0, 0));
+                }
+            }
+            Encounters::Repulsors(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::repulsor(ecs, 0, 0));
+                }
+            }
+            Encounters::GiantHead(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::giant_head(ecs, 0, 0));
+                }
+            }
+            Encounters::Nemesis(num) => {
+                for _ in 0 .. num {
+                    spawned.push(mobs::nemesis(ecs, 0, 0));
+                }
+            }
+            Encounters::AwakenedOne => {
+                spawned.push(mobs::awakened_one(ecs, 0, 0));
+            }
         }
 
         return spawned
     }
+}
+
+/// Single monsters that can be spawned in the middle of combat, such as the halves of a split slime
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Mob {
+    AcidSlimeL,
+    SpikeSlimeL,
+    AcidSlimeM,
+    SpikeSlimeM,
+    AcidSlimeS,
+    SpikeSlimeS,
+}
+
+impl Mob {
+    pub fn spawn(self, ecs: &mut World, x: i32, y: i32) -> Entity {
+        match self {
+            Mob::AcidSlimeL => mobs::acid_slime_l(ecs, x, y),
+            Mob::SpikeSlimeL => mobs::spike_slime_l(ecs, x, y),
+            Mob::AcidSlimeM => mobs::acid_slime_m(ecs, x, y),
+            Mob::SpikeSlimeM => mobs::spike_slime_m(ecs, x, y),
+            Mob::AcidSlimeS => mobs::acid_slime_s(ecs, x, y),
+            Mob::SpikeSlimeS => mobs::spike_slime_s(ecs, x, y),
+        }
+    }
 }
\ No newline at end of file
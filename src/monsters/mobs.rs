@@ -4,7 +4,7 @@ use rltk::{RGB, RandomNumberGenerator};
 
 use super::super::{
     Name, Position, Renderable,
-    creature, effects, monsters, saveload,
+    creature, effects, monsters, passive, saveload,
 };
 
 fn build_monster<S: ToString>(ecs: &mut World, name: S, x: i32, y: i32, glyph: rltk::FontCharType, fg: RGB) -> EntityBuilder {
@@ -21,15 +21,16 @@ fn build_monster<S: ToString>(ecs: &mut World, name: S, x: i32, y: i32, glyph: r
         .with(creature::Monster{})
         .with(creature::Viewshed{ visible_tiles: Vec::new(), range: 8, dirty: true})
         .with(creature::BlocksTile{})
+        .with(creature::Awareness::new())
         .marked::<SimpleMarker<saveload::SerializeMe>>()
 }
 
 pub fn cultist(ecs: &mut World, x: i32, y: i32) -> Entity {
     let hp = ecs.write_resource::<RandomNumberGenerator>().range(48, 55);
 
-    let attack_incantation = monsters::Attacks::BuffStrength{
+    let attack_incantation = monsters::Attacks::GainPassive{
         name: "Incantation".to_string(),
-        amount: 4,
+        passive: passive::Passive::Ritual{ amount: 3 },
         range: 2
     };
     let attack_dark_strike = monsters::Attacks::NormalAttack{
@@ -40,8 +41,8 @@ pub fn cultist(ecs: &mut World, x: i32, y: i32) -> Entity {
     let intent = attack_incantation.clone().to_attack(ecs);
 
     let attack_cycle = creature::AttackCycle::new_weighted()
-        .add_weighted(attack_incantation, 1)
-        .add_weighted(attack_dark_strike, 4);
+        .add_weighted(attack_dark_strike, 1)
+        .with_opening(attack_incantation);
 
     build_monster(ecs, "Cultist", x, y, rltk::to_cp437('c'), RGB::named(rltk::RED))
         .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
@@ -76,9 +77,11 @@ pub fn jaw_worm(ecs: &mut World, x: i32, y: i32) -> Entity {
     let intent = attack_chomp.clone().to_attack(ecs);
 
     let attack_cycle = creature::AttackCycle::new_weighted()
-        .add_weighted(attack_chomp, 5)
+        .add_weighted(attack_chomp.clone(), 5)
         .add_weighted(attack_thrash, 6)
-        .add_weighted(attack_bellow, 9);
+        .add_weighted(attack_bellow, 9)
+        .with_opening(attack_chomp)
+        .with_max_repeats(2);
 
     build_monster(ecs, "Jaw Worm", x, y, rltk::to_cp437('j'), RGB::named(rltk::RED))
         .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
@@ -103,6 +106,7 @@ pub fn red_louse(ecs: &mut World, x: i32, y: i32) -> Entity {
         amount: 4,
         range: 2
     };
+    let curl_up = ecs.write_resource::<RandomNumberGenerator>().range(3, 8);
     let intent = attack_bite.clone().to_attack(ecs);
 
     let attack_cycle = creature::AttackCycle::new_weighted()
@@ -116,6 +120,7 @@ pub fn red_louse(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(passive::Passives::new(vec![passive::Passive::CurlUp{ amount: curl_up }]))
         .build()
 }
 
@@ -132,6 +137,7 @@ pub fn green_louse(ecs: &mut World, x: i32, y: i32) -> Entity {
         turns: 2,
         range: 2
     };
+    let curl_up = ecs.write_resource::<RandomNumberGenerator>().range(3, 8);
     let intent = attack_bite.clone().to_attack(ecs);
 
     let attack_cycle = creature::AttackCycle::new_weighted()
@@ -145,6 +151,7 @@ pub fn green_louse(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(passive::Passives::new(vec![passive::Passive::CurlUp{ amount: curl_up }]))
         .build()
 }
 
@@ -182,6 +189,8 @@ pub fn acid_slime_l(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(creature::Splits{ into: vec![monsters::Mob::AcidSlimeM, monsters::Mob::AcidSlimeM], ready: false })
+        .with(creature::Behaviour::Kiter)
         .build()
 }
 
@@ -213,6 +222,7 @@ pub fn spike_slime_l(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(creature::Splits{ into: vec![monsters::Mob::SpikeSlimeM, monsters::Mob::SpikeSlimeM], ready: false })
         .build()
 }
 
@@ -250,6 +260,8 @@ pub fn acid_slime_m(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(creature::Splits{ into: vec![monsters::Mob::AcidSlimeS, monsters::Mob::AcidSlimeS], ready: false })
+        .with(creature::Behaviour::Kiter)
         .build()
 }
 
@@ -281,6 +293,7 @@ pub fn spike_slime_m(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(creature::Splits{ into: vec![monsters::Mob::SpikeSlimeS, monsters::Mob::SpikeSlimeS], ready: false })
         .build()
 }
 
@@ -356,6 +369,9 @@ pub fn mad_gremlin(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(passive::Passives::new(vec![passive::Passive::Angry{ amount: 1 }]))
+        .with(creature::Behaviour::Rusher)
+        .with(creature::Gremlin{})
         .build()
 }
 
@@ -367,10 +383,15 @@ pub fn sneaky_gremlin(ecs: &mut World, x: i32, y: i32) -> Entity {
         amount: 4,
         range: 1
     };
+    let attack_slip_past = monsters::Attacks::Swap{
+        name: "Slip Past".to_string(),
+        range: 1
+    };
     let intent = attack_puncture.clone().to_attack(ecs);
 
-    let attack_cycle = creature::AttackCycle::new_sequential()
-        .add_sequential(attack_puncture);
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_puncture, 3)
+        .add_weighted(attack_slip_past, 1);
 
     build_monster(ecs, "Sneaky Gremlin", x, y, rltk::to_cp437('g'), RGB::named(rltk::PINK))
         .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
@@ -379,6 +400,8 @@ pub fn sneaky_gremlin(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(creature::Behaviour::Skittish{ flee_percent: 30 })
+        .with(creature::Gremlin{})
         .build()
 }
 
@@ -403,6 +426,8 @@ pub fn fat_gremlin(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(creature::Behaviour::Skittish{ flee_percent: 30 })
+        .with(creature::Gremlin{})
         .build()
 }
 
@@ -433,16 +458,18 @@ pub fn gremlin_wizard(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(creature::Behaviour::Kiter)
+        .with(creature::Gremlin{})
         .build()
 }
 
 pub fn shield_gremlin(ecs: &mut World, x: i32, y: i32) -> Entity {
     let hp = ecs.write_resource::<RandomNumberGenerator>().range(12, 16);
 
-    let attack_protect = monsters::Attacks::GainBlock{
+    let attack_protect = monsters::Attacks::BlockAlly{
         name: "Protect".to_string(),
         amount: 7,
-        range: 1
+        range: 3
     };
     let attack_shield_bash = monsters::Attacks::AttackAndBlock{
         name: "Shield Bash".to_string(),
@@ -463,6 +490,37 @@ pub fn shield_gremlin(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(creature::Gremlin{})
+        .build()
+}
+
+pub fn gremlin_leader(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(140, 149);
+
+    let attack_encourage = monsters::Attacks::BuffAllies{
+        name: "Encourage".to_string(),
+        amount: 3,
+        range: 4
+    };
+    let attack_stab = monsters::Attacks::NormalAttack{
+        name: "Stab".to_string(),
+        amount: 18,
+        range: 1
+    };
+    let intent = attack_encourage.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_encourage, 2)
+        .add_weighted(attack_stab, 1);
+
+    build_monster(ecs, "Gremlin Leader", x, y, rltk::to_cp437('G'), RGB::named(rltk::GOLD))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Gremlin{})
         .build()
 }
 
@@ -480,11 +538,23 @@ pub fn blue_slaver(ecs: &mut World, x: i32, y: i32) -> Entity {
         turns: 1,
         range: 1
     };
+    let attack_lasso = monsters::Attacks::Pull{
+        name: "Lasso".to_string(),
+        distance: 2,
+        range: 3
+    };
+    let attack_hobble = monsters::Attacks::ApplySlowed{
+        name: "Hobble".to_string(),
+        turns: 4,
+        range: 1
+    };
     let intent = attack_stab.clone().to_attack(ecs);
 
     let attack_cycle = creature::AttackCycle::new_weighted()
         .add_weighted(attack_stab, 3)
-        .add_weighted(attack_rake, 2);
+        .add_weighted(attack_rake, 2)
+        .add_weighted(attack_lasso, 1)
+        .add_weighted(attack_hobble, 1);
 
     build_monster(ecs, "Slaver", x, y, rltk::to_cp437('l'), RGB::named(rltk::BLUE))
         .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
@@ -510,11 +580,17 @@ pub fn red_slaver(ecs: &mut World, x: i32, y: i32) -> Entity {
         turns: 1,
         range: 1
     };
+    let attack_entangle = monsters::Attacks::ApplyEntangled{
+        name: "Entangle".to_string(),
+        turns: 2,
+        range: 3
+    };
     let intent = attack_stab.clone().to_attack(ecs);
 
     let attack_cycle = creature::AttackCycle::new_weighted()
         .add_weighted(attack_stab, 3)
-        .add_weighted(attack_scrape, 2);
+        .add_weighted(attack_scrape, 2)
+        .add_weighted(attack_entangle, 1);
 
     build_monster(ecs, "Slaver", x, y, rltk::to_cp437('l'), RGB::named(rltk::RED))
         .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
@@ -552,24 +628,27 @@ pub fn fungi_beast(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(passive::Passives::new(vec![passive::Passive::SporeCloud{ turns: 2, radius: 2 }]))
         .build()
 }
 
 pub fn looter(ecs: &mut World, x: i32, y: i32) -> Entity {
     let hp = ecs.write_resource::<RandomNumberGenerator>().range(44, 49);
 
-    let attack_mug = monsters::Attacks::NormalAttack{
+    let attack_mug = monsters::Attacks::AttackAndStealGold{
         name: "Mug".to_string(),
         amount: 10,
-        range: 1
+        range: 1,
+        gold: 15
     };
-    let attack_lunge = monsters::Attacks::NormalAttack{
+    let attack_lunge = monsters::Attacks::AttackAndStealGold{
         name: "Lunge".to_string(),
         amount: 12,
-        range: 2
+        range: 2,
+        gold: 15
     };
-    let attack_smoke_bomb = monsters::Attacks::GainBlock{
-        name: "Smoke Bomg".to_string(),
+    let attack_smoke_bomb = monsters::Attacks::BlockAndFlee{
+        name: "Smoke Bomb".to_string(),
         amount: 6,
         range: 2
     };
@@ -587,5 +666,861 @@ pub fn looter(ecs: &mut World, x: i32, y: i32) -> Entity {
         })
         .with(attack_cycle)
         .with(creature::Intent{ intent, used: false })
+        .with(creature::Behaviour::Skittish{ flee_percent: 25 })
+        .build()
+}
+
+pub fn gremlin_nob(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(82, 87);
+
+    let attack_bellow = monsters::Attacks::BuffStrength{
+        name: "Bellow".to_string(),
+        amount: 3,
+        range: 2
+    };
+    let attack_rush = monsters::Attacks::NormalAttack{
+        name: "Rush".to_string(),
+        amount: 14,
+        range: 1
+    };
+    let attack_skull_bash = monsters::Attacks::AttackAndApplyVulnerable{
+        name: "Skull Bash".to_string(),
+        amount: 6,
+        turns: 2,
+        range: 1
+    };
+    let intent = attack_bellow.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_bellow, 1)
+        .add_weighted(attack_rush, 2)
+        .add_weighted(attack_skull_bash, 1);
+
+    build_monster(ecs, "Gremlin Nob", x, y, rltk::to_cp437('N'), RGB::named(rltk::RED))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Enrage{ amount: 2 })
+        .with(creature::Elite{})
+        .with(creature::Behaviour::Rusher)
+        .with(creature::Gremlin{})
+        .build()
+}
+
+pub fn lagavulin(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(109, 112);
+
+    let attack_sleep = monsters::Attacks::Pass{
+        name: "Sleep".to_string(),
+        range: 1
+    };
+    let attack_siphon_soul = monsters::Attacks::DebuffStrengthAndDexterity{
+        name: "Siphon Soul".to_string(),
+        strength_amount: 1,
+        dexterity_amount: 1,
+        range: 2
+    };
+    let attack_attack = monsters::Attacks::NormalAttack{
+        name: "Attack".to_string(),
+        amount: 18,
+        range: 1
+    };
+    let intent = attack_sleep.to_attack(ecs);
+
+    // Waking up uses a turn and advances the cycle past Siphon Soul
+    let attack_cycle = creature::AttackCycle::new_sequential()
+        .add_sequential(attack_siphon_soul)
+        .add_sequential(attack_attack.clone())
+        .add_sequential(attack_attack);
+
+    build_monster(ecs, "Lagavulin", x, y, rltk::to_cp437('L'), RGB::named(rltk::LIGHT_GRAY))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 8,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Asleep{ turns: 3 })
+        .with(creature::Elite{})
+        .build()
+}
+
+pub fn sentry(ecs: &mut World, x: i32, y: i32, beam_first: bool) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(38, 43);
+
+    let attack_bolt = monsters::Attacks::GiveCard{
+        name: "Bolt".to_string(),
+        card: effects::GainableCard::Dazed,
+        number: 2,
+        range: 3
+    };
+    let attack_beam = monsters::Attacks::NormalAttack{
+        name: "Beam".to_string(),
+        amount: 9,
+        range: 3
+    };
+
+    let mut attack_cycle = creature::AttackCycle::new_sequential()
+        .add_sequential(attack_bolt)
+        .add_sequential(attack_beam);
+
+    // Neighbouring sentries alternate so that a Bolt and a Beam always come together
+    if beam_first { attack_cycle.cycle = 1; }
+    let intent = attack_cycle.attacks[attack_cycle.cycle].clone().to_attack(ecs);
+
+    build_monster(ecs, "Sentry", x, y, rltk::to_cp437('o'), RGB::named(rltk::YELLOW))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Stationary{})
+        .with(creature::Elite{})
+        .build()
+}
+
+pub fn slime_boss(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(140, 141);
+
+    let attack_goop_spray = monsters::Attacks::AttackAndGiveCard{
+        name: "Goop Spray".to_string(),
+        amount: 0,
+        card: effects::GainableCard::Slimed,
+        number: 3,
+        range: 3
+    };
+    let attack_preparing = monsters::Attacks::Pass{
+        name: "Preparing".to_string(),
+        range: 3
+    };
+    let attack_slam = monsters::Attacks::NormalAttack{
+        name: "Slam".to_string(),
+        amount: 35,
+        range: 1
+    };
+    let intent = attack_goop_spray.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_sequential()
+        .add_sequential(attack_goop_spray)
+        .add_sequential(attack_preparing)
+        .add_sequential(attack_slam);
+
+    build_monster(ecs, "Slime Boss", x, y, rltk::to_cp437('S'), RGB::named(rltk::LIME_GREEN))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Splits{ into: vec![monsters::Mob::AcidSlimeL, monsters::Mob::SpikeSlimeL], ready: false })
+        .with(creature::Boss{})
+        .build()
+}
+
+pub fn the_guardian(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(240, 241);
+
+    let attack_charging_up = monsters::Attacks::GainBlock{
+        name: "Charging Up".to_string(),
+        amount: 9,
+        range: 3
+    };
+    let attack_fierce_bash = monsters::Attacks::AttackAndPush{
+        name: "Fierce Bash".to_string(),
+        amount: 32,
+        distance: 2,
+        collision_damage: 8,
+        range: 1
+    };
+    let attack_vent_steam = monsters::Attacks::ApplyWeak{
+        name: "Vent Steam".to_string(),
+        turns: 2,
+        range: 3
+    };
+    let attack_whirlwind = monsters::Attacks::NormalAttack{
+        name: "Whirlwind".to_string(),
+        amount: 20,
+        range: 1
+    };
+    let offensive = vec![attack_charging_up, attack_fierce_bash, attack_vent_steam, attack_whirlwind];
+
+    let attack_defensive_mode = monsters::Attacks::Pass{
+        name: "Defensive Mode".to_string(),
+        range: 3
+    };
+    let attack_roll_attack = monsters::Attacks::NormalAttack{
+        name: "Roll Attack".to_string(),
+        amount: 9,
+        range: 1
+    };
+    let attack_twin_slam = monsters::Attacks::NormalAttack{
+        name: "Twin Slam".to_string(),
+        amount: 16,
+        range: 1
+    };
+    let defensive = vec![attack_defensive_mode, attack_roll_attack, attack_twin_slam];
+
+    let intent = offensive[0].clone().to_attack(ecs);
+
+    let mut attack_cycle = creature::AttackCycle::new_sequential();
+    attack_cycle.switch_sequential(offensive.clone());
+
+    build_monster(ecs, "The Guardian", x, y, rltk::to_cp437('G'), RGB::named(rltk::LIGHT_GRAY))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::ModeShift{ offensive, defensive, threshold: 30, damage_taken: 0, block: 20, defending: false })
+        .with(creature::Boss{})
+        .build()
+}
+
+pub fn hexaghost(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(250, 251);
+
+    let attack_activate = monsters::Attacks::Pass{
+        name: "Activate".to_string(),
+        range: 3
+    };
+    let attack_divider = monsters::Attacks::Divider{
+        name: "Divider".to_string(),
+        hits: 6,
+        range: 3
+    };
+    let attack_sear = monsters::Attacks::NormalAttack{
+        name: "Sear".to_string(),
+        amount: 6,
+        range: 3
+    };
+    let attack_tackle = monsters::Attacks::NormalAttack{
+        name: "Tackle".to_string(),
+        amount: 10,
+        range: 1
+    };
+    let attack_inflame = monsters::Attacks::BlockAndBuffStrength{
+        name: "Inflame".to_string(),
+        block_amount: 12,
+        buff_amount: 2,
+        range: 3
+    };
+    let attack_inferno = monsters::Attacks::NormalAttack{
+        name: "Inferno".to_string(),
+        amount: 18,
+        range: 3
+    };
+    let intent = attack_activate.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_sequential()
+        .add_sequential(attack_activate)
+        .add_sequential(attack_divider)
+        .add_sequential(attack_sear.clone())
+        .add_sequential(attack_tackle.clone())
+        .add_sequential(attack_sear.clone())
+        .add_sequential(attack_inflame)
+        .add_sequential(attack_tackle)
+        .add_sequential(attack_sear)
+        .add_sequential(attack_inferno);
+
+    build_monster(ecs, "Hexaghost", x, y, rltk::to_cp437('H'), RGB::named(rltk::PURPLE))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Boss{})
+        .build()
+}
+
+pub fn byrd(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(25, 32);
+
+    let attack_peck = monsters::Attacks::NormalAttack{
+        name: "Peck".to_string(),
+        amount: 6,
+        range: 1
+    };
+    let attack_caw = monsters::Attacks::BuffStrength{
+        name: "Caw".to_string(),
+        amount: 1,
+        range: 2
+    };
+    let attack_swoop = monsters::Attacks::NormalAttack{
+        name: "Swoop".to_string(),
+        amount: 12,
+        range: 2
+    };
+    let intent = attack_peck.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_peck, 5)
+        .add_weighted(attack_caw, 3)
+        .add_weighted(attack_swoop, 2);
+
+    build_monster(ecs, "Byrd", x, y, rltk::to_cp437('b'), RGB::named(rltk::LIGHT_GRAY))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Behaviour::Kiter)
+        .build()
+}
+
+pub fn chosen(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(95, 100);
+
+    let attack_poke = monsters::Attacks::NormalAttack{
+        name: "Poke".to_string(),
+        amount: 5,
+        range: 1
+    };
+    let attack_debilitate = monsters::Attacks::AttackAndApplyVulnerable{
+        name: "Debilitate".to_string(),
+        amount: 10,
+        turns: 2,
+        range: 1
+    };
+    let attack_drain = monsters::Attacks::ApplyWeak{
+        name: "Drain".to_string(),
+        turns: 3,
+        range: 2
+    };
+    let attack_zap = monsters::Attacks::NormalAttack{
+        name: "Zap".to_string(),
+        amount: 18,
+        range: 2
+    };
+    let intent = attack_poke.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_poke, 3)
+        .add_weighted(attack_debilitate, 3)
+        .add_weighted(attack_drain, 2)
+        .add_weighted(attack_zap, 2);
+
+    build_monster(ecs, "Chosen", x, y, rltk::to_cp437('C'), RGB::named(rltk::PURPLE))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Behaviour::Kiter)
+        .build()
+}
+
+pub fn shelled_parasite(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(68, 73);
+
+    let attack_double_strike = monsters::Attacks::NormalAttack{
+        name: "Double Strike".to_string(),
+        amount: 12,
+        range: 1
+    };
+    let attack_suck = monsters::Attacks::AttackAndBlock{
+        name: "Suck".to_string(),
+        damage_amount: 10,
+        block_amount: 10,
+        range: 1
+    };
+    let attack_fell = monsters::Attacks::AttackAndApplyVulnerable{
+        name: "Fell".to_string(),
+        amount: 18,
+        turns: 2,
+        range: 1
+    };
+    let intent = attack_double_strike.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_double_strike, 4)
+        .add_weighted(attack_suck, 4)
+        .add_weighted(attack_fell, 2);
+
+    build_monster(ecs, "Shelled Parasite", x, y, rltk::to_cp437('p'), RGB::named(rltk::ORANGE))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .build()
+}
+
+pub fn snake_plant(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(75, 80);
+
+    let attack_chomp = monsters::Attacks::NormalAttack{
+        name: "Chomp".to_string(),
+        amount: 21,
+        range: 1
+    };
+    let attack_enfeebling_spores = monsters::Attacks::ApplyFrail{
+        name: "Enfeebling Spores".to_string(),
+        turns: 2,
+        range: 2
+    };
+    let attack_grasping_roots = monsters::Attacks::ApplyRooted{
+        name: "Grasping Roots".to_string(),
+        turns: 3,
+        range: 3
+    };
+    let intent = attack_chomp.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_chomp, 10)
+        .add_weighted(attack_enfeebling_spores, 6)
+        .add_weighted(attack_grasping_roots, 4);
+
+    build_monster(ecs, "Snake Plant", x, y, rltk::to_cp437('P'), RGB::named(rltk::GREEN))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Behaviour::Guard{ provoked: false })
+        .build()
+}
+
+pub fn centurion(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(76, 81);
+
+    let attack_slash = monsters::Attacks::NormalAttack{
+        name: "Slash".to_string(),
+        amount: 12,
+        range: 1
+    };
+    let attack_fury = monsters::Attacks::NormalAttack{
+        name: "Fury".to_string(),
+        amount: 18,
+        range: 1
+    };
+    let attack_defend = monsters::Attacks::GainBlock{
+        name: "Defend".to_string(),
+        amount: 15,
+        range: 2
+    };
+    let intent = attack_slash.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_slash, 6)
+        .add_weighted(attack_fury, 2)
+        .add_weighted(attack_defend, 2);
+
+    build_monster(ecs, "Centurion", x, y, rltk::to_cp437('c'), RGB::named(rltk::YELLOW))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Behaviour::Guard{ provoked: false })
+        .build()
+}
+
+pub fn mystic(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(48, 57);
+
+    let attack_attack_debuff = monsters::Attacks::AttackAndApplyWeak{
+        name: "Attack Debuff".to_string(),
+        amount: 8,
+        turns: 2,
+        range: 2
+    };
+    let attack_buff = monsters::Attacks::BuffStrength{
+        name: "Buff".to_string(),
+        amount: 2,
+        range: 2
+    };
+    let attack_protect = monsters::Attacks::GainBlock{
+        name: "Protect".to_string(),
+        amount: 12,
+        range: 2
+    };
+    let intent = attack_attack_debuff.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_attack_debuff, 4)
+        .add_weighted(attack_buff, 3)
+        .add_weighted(attack_protect, 3);
+
+    build_monster(ecs, "Mystic", x, y, rltk::to_cp437('m'), RGB::named(rltk::CYAN))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Behaviour::Kiter)
+        .build()
+}
+
+pub fn book_of_stabbing(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(160, 165);
+
+    let attack_multi_stab = monsters::Attacks::NormalAttack{
+        name: "Multi-Stab".to_string(),
+        amount: 18,
+        range: 1
+    };
+    let attack_single_stab = monsters::Attacks::NormalAttack{
+        name: "Single Stab".to_string(),
+        amount: 21,
+        range: 1
+    };
+    let intent = attack_multi_stab.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_multi_stab, 17)
+        .add_weighted(attack_single_stab, 3);
+
+    build_monster(ecs, "Book of Stabbing", x, y, rltk::to_cp437('B'), RGB::named(rltk::WHITE))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Elite{})
+        .build()
+}
+
+pub fn taskmaster(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(54, 61);
+
+    let attack_scouring_whip = monsters::Attacks::AttackAndGiveCard{
+        name: "Scouring Whip".to_string(),
+        amount: 7,
+        card: effects::GainableCard::Slimed,
+        number: 1,
+        range: 2
+    };
+    let intent = attack_scouring_whip.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_sequential()
+        .add_sequential(attack_scouring_whip);
+
+    build_monster(ecs, "Taskmaster", x, y, rltk::to_cp437('T'), RGB::named(rltk::RED))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Elite{})
+        .build()
+}
+
+pub fn the_champ(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(420, 421);
+
+    let attack_face_slap = monsters::Attacks::AttackAndApplyWeak{
+        name: "Face Slap".to_string(),
+        amount: 12,
+        turns: 2,
+        range: 1
+    };
+    let attack_defensive_stance = monsters::Attacks::GainBlock{
+        name: "Defensive Stance".to_string(),
+        amount: 15,
+        range: 2
+    };
+    let attack_heavy_slash = monsters::Attacks::NormalAttack{
+        name: "Heavy Slash".to_string(),
+        amount: 16,
+        range: 1
+    };
+    let attack_gloat = monsters::Attacks::BuffStrength{
+        name: "Gloat".to_string(),
+        amount: 2,
+        range: 2
+    };
+    let attack_taunt = monsters::Attacks::AttackAndApplyVulnerable{
+        name: "Taunt".to_string(),
+        amount: 0,
+        turns: 2,
+        range: 2
+    };
+    let attack_anger = monsters::Attacks::BuffStrength{
+        name: "Anger".to_string(),
+        amount: 6,
+        range: 2
+    };
+    let attack_execute = monsters::Attacks::NormalAttack{
+        name: "Execute".to_string(),
+        amount: 30,
+        range: 1
+    };
+    let intent = attack_face_slap.clone().to_attack(ecs);
+
+    // Below half health The Champ gets angry and starts executing
+    let attack_cycle_angry = creature::AttackCycle::new_sequential()
+        .add_sequential(attack_execute)
+        .add_sequential(attack_heavy_slash.clone())
+        .add_sequential(attack_face_slap.clone())
+        .with_opening(attack_anger);
+
+    let attack_cycle = creature::AttackCycle::new_sequential()
+        .add_sequential(attack_face_slap)
+        .add_sequential(attack_defensive_stance)
+        .add_sequential(attack_heavy_slash)
+        .add_sequential(attack_gloat)
+        .add_sequential(attack_taunt)
+        .with_phase(50, attack_cycle_angry);
+
+    build_monster(ecs, "The Champ", x, y, rltk::to_cp437('K'), RGB::named(rltk::GOLD))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Boss{})
+        .build()
+}
+
+pub fn darkling(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(48, 57);
+
+    let attack_nip = monsters::Attacks::NormalAttack{
+        name: "Nip".to_string(),
+        amount: 9,
+        range: 1
+    };
+    let attack_chomp = monsters::Attacks::NormalAttack{
+        name: "Chomp".to_string(),
+        amount: 16,
+        range: 1
+    };
+    let attack_harden = monsters::Attacks::BlockAndBuffStrength{
+        name: "Harden".to_string(),
+        block_amount: 12,
+        buff_amount: 2,
+        range: 2
+    };
+    let intent = attack_nip.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_nip, 4)
+        .add_weighted(attack_chomp, 3)
+        .add_weighted(attack_harden, 3);
+
+    build_monster(ecs, "Darkling", x, y, rltk::to_cp437('d'), RGB::named(rltk::DARK_GRAY))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .build()
+}
+
+pub fn orb_walker(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(90, 97);
+
+    let attack_laser = monsters::Attacks::AttackAndGiveCard{
+        name: "Laser".to_string(),
+        amount: 10,
+        card: effects::GainableCard::Slimed,
+        number: 1,
+        range: 3
+    };
+    let attack_claw = monsters::Attacks::NormalAttack{
+        name: "Claw".to_string(),
+        amount: 15,
+        range: 1
+    };
+    let intent = attack_laser.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_laser, 3)
+        .add_weighted(attack_claw, 2);
+
+    build_monster(ecs, "Orb Walker", x, y, rltk::to_cp437('o'), RGB::named(rltk::ORANGE))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .build()
+}
+
+pub fn spiker(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(42, 57);
+
+    let attack_cut = monsters::Attacks::NormalAttack{
+        name: "Cut".to_string(),
+        amount: 7,
+        range: 1
+    };
+    let attack_spike = monsters::Attacks::BlockAndBuffStrength{
+        name: "Spike".to_string(),
+        block_amount: 6,
+        buff_amount: 2,
+        range: 2
+    };
+    let intent = attack_cut.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_cut, 1)
+        .add_weighted(attack_spike, 3);
+
+    build_monster(ecs, "Spiker", x, y, rltk::to_cp437('^'), RGB::named(rltk::LIGHT_GRAY))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .build()
+}
+
+pub fn repulsor(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(29, 36);
+
+    let attack_repulse = monsters::Attacks::ApplyWeak{
+        name: "Repulse".to_string(),
+        turns: 1,
+        range: 3
+    };
+    let attack_bash = monsters::Attacks::NormalAttack{
+        name: "Bash".to_string(),
+        amount: 11,
+        range: 1
+    };
+    let intent = attack_repulse.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_repulse, 4)
+        .add_weighted(attack_bash, 1);
+
+    build_monster(ecs, "Repulsor", x, y, rltk::to_cp437('r'), RGB::named(rltk::LIGHT_BLUE))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .build()
+}
+
+pub fn giant_head(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(250, 251);
+
+    let attack_count = monsters::Attacks::NormalAttack{
+        name: "Count".to_string(),
+        amount: 13,
+        range: 2
+    };
+    let attack_glare = monsters::Attacks::ApplyWeak{
+        name: "Glare".to_string(),
+        turns: 1,
+        range: 3
+    };
+    let attack_it_is_time = monsters::Attacks::NormalAttack{
+        name: "It Is Time".to_string(),
+        amount: 30,
+        range: 2
+    };
+    let intent = attack_count.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_sequential()
+        .add_sequential(attack_count.clone())
+        .add_sequential(attack_glare.clone())
+        .add_sequential(attack_count.clone())
+        .add_sequential(attack_glare.clone())
+        .add_sequential(attack_it_is_time);
+
+    build_monster(ecs, "Giant Head", x, y, rltk::to_cp437('H'), RGB::named(rltk::GREY))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Elite{})
+        .build()
+}
+
+pub fn nemesis(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(185, 186);
+
+    let attack_tri_attack = monsters::Attacks::NormalAttack{
+        name: "Tri Attack".to_string(),
+        amount: 18,
+        range: 1
+    };
+    let attack_debuff = monsters::Attacks::AttackAndGiveCard{
+        name: "Debuff".to_string(),
+        amount: 0,
+        card: effects::GainableCard::Slimed,
+        number: 3,
+        range: 3
+    };
+    let attack_scythe = monsters::Attacks::NormalAttack{
+        name: "Scythe".to_string(),
+        amount: 45,
+        range: 1
+    };
+    let intent = attack_tri_attack.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_sequential()
+        .add_sequential(attack_tri_attack)
+        .add_sequential(attack_debuff)
+        .add_sequential(attack_scythe);
+
+    build_monster(ecs, "Nemesis", x, y, rltk::to_cp437('X'), RGB::named(rltk::MAGENTA))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Elite{})
+        .build()
+}
+
+pub fn awakened_one(ecs: &mut World, x: i32, y: i32) -> Entity {
+    let hp = ecs.write_resource::<RandomNumberGenerator>().range(300, 301);
+
+    let attack_slash = monsters::Attacks::NormalAttack{
+        name: "Slash".to_string(),
+        amount: 20,
+        range: 1
+    };
+    let attack_soul_strike = monsters::Attacks::NormalAttack{
+        name: "Soul Strike".to_string(),
+        amount: 24,
+        range: 2
+    };
+    let attack_curiosity = monsters::Attacks::BuffStrength{
+        name: "Curiosity".to_string(),
+        amount: 2,
+        range: 2
+    };
+    let intent = attack_slash.clone().to_attack(ecs);
+
+    let attack_cycle = creature::AttackCycle::new_weighted()
+        .add_weighted(attack_slash, 5)
+        .add_weighted(attack_soul_strike, 3)
+        .add_weighted(attack_curiosity, 2);
+
+    build_monster(ecs, "Awakened One", x, y, rltk::to_cp437('W'), RGB::named(rltk::BLUE))
+        .with(creature::CombatStats{ max_hp: hp, hp: hp, block: 0,
+            base_strength: 0, strength: 0,
+            base_dexterity: 0, dexterity: 0
+        })
+        .with(attack_cycle)
+        .with(creature::Intent{ intent, used: false })
+        .with(creature::Boss{})
         .build()
 }
\ No newline at end of file
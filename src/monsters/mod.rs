@@ -3,6 +3,6 @@ mod encounters;
 mod mobs;
 mod spawntables;
 
-pub use attacks::Attacks;
-pub use encounters::Encounters;
-pub use spawntables::spawn_table;
\ No newline at end of file
+pub use attacks::{Attacks, allies_in_range, supported_allies, weakest_ally_in_range};
+pub use encounters::{Encounters, Mob};
+pub use spawntables::{spawn_table, elite_table, boss_table};
\ No newline at end of file
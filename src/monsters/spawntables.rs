@@ -1,27 +1,88 @@
 use super::Encounters;
 
-use super::super::{util::RandomTable};
+use super::super::{util::RandomTable, act::{self, Act}};
 
 pub fn spawn_table(floor: i32) -> RandomTable<Encounters> {
-    match floor {
-        1 => {
-            RandomTable::new()
-                .add(Encounters::Cultist(1), 1)
-                .add(Encounters::JawWorm(1), 1)
-                .add(Encounters::Louses(2), 1)
-                .add(Encounters::SlimePair(1), 1)
-        }
-        _ => {
-            RandomTable::new()
-                .add(Encounters::GremlinGang(4), 2)
-                .add(Encounters::LargeSlime(1), 4)
-                .add(Encounters::SmallSlimes(5), 2)
-                .add(Encounters::Slaver(1), 6)
-                .add(Encounters::Louses(3), 4)
-                .add(Encounters::FungiBeast(2), 4)
-                .add(Encounters::ExordiumThugs(1), 3)
-                .add(Encounters::ExordiumWildlife(1), 3)
-                .add(Encounters::Looter(1), 4)
+    match act::Act::from_floor(floor) {
+        Act::Exordium => {
+            match act::floor_in_act(floor) {
+                1 => {
+                    RandomTable::new()
+                        .add(Encounters::Cultist(1), 1)
+                        .add(Encounters::JawWorm(1), 1)
+                        .add(Encounters::Louses(2), 1)
+                        .add(Encounters::SlimePair(1), 1)
+                }
+                _ => {
+                    RandomTable::new()
+                        .add(Encounters::GremlinGang(4), 2)
+                        .add(Encounters::LargeSlime(1), 4)
+                        .add(Encounters::SmallSlimes(5), 2)
+                        .add(Encounters::Slaver(1), 6)
+                        .add(Encounters::Louses(3), 4)
+                        .add(Encounters::FungiBeast(2), 4)
+                        .add(Encounters::ExordiumThugs(1), 3)
+                        .add(Encounters::ExordiumWildlife(1), 3)
+                        .add(Encounters::Looter(1), 4)
+                }
+            }
+        }
+        Act::City => {
+            RandomTable::new()
+                .add(Encounters::Byrds(3), 4)
+                .add(Encounters::Chosen(1), 4)
+                .add(Encounters::ShelledParasite(1), 3)
+                .add(Encounters::SnakePlant(1), 3)
+                .add(Encounters::CenturionAndMystic(1), 3)
+                .add(Encounters::Slaver(2), 2)
+                .add(Encounters::GremlinLeader(1), 2)
+        }
+        Act::Beyond => {
+            RandomTable::new()
+                .add(Encounters::Darklings(3), 4)
+                .add(Encounters::OrbWalker(1), 4)
+                .add(Encounters::Spikers(2), 3)
+                .add(Encounters::Repulsors(3), 3)
+        }
+    }
+}
+
+pub fn elite_table(floor: i32) -> RandomTable<Encounters> {
+    match act::Act::from_floor(floor) {
+        Act::Exordium => {
+            RandomTable::new()
+                .add(Encounters::GremlinNob(1), 1)
+                .add(Encounters::Lagavulin(1), 1)
+                .add(Encounters::Sentries, 1)
+        }
+        Act::City => {
+            RandomTable::new()
+                .add(Encounters::BookOfStabbing(1), 1)
+                .add(Encounters::Taskmaster(1), 1)
+        }
+        Act::Beyond => {
+            RandomTable::new()
+                .add(Encounters::GiantHead(1), 1)
+                .add(Encounters::Nemesis(1), 1)
+        }
+    }
+}
+
+pub fn boss_table(floor: i32) -> RandomTable<Encounters> {
+    match act::Act::from_floor(floor) {
+        Act::Exordium => {
+            RandomTable::new()
+                .add(Encounters::SlimeBoss, 1)
+                .add(Encounters::TheGuardian, 1)
+                .add(Encounters::Hexaghost, 1)
+        }
+        Act::City => {
+            RandomTable::new()
+                .add(Encounters::TheChamp, 1)
+        }
+        Act::Beyond => {
+            RandomTable::new()
+                .add(Encounters::AwakenedOne, 1)
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,114 @@
+use specs::prelude::*;
+use rltk::{RGB, Rltk, Point};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::map::{Map, TileType};
+
+pub const ATTACK_NOISE: i32 = 6;
+pub const DAMAGE_NOISE: i32 = 4;
+pub const SHATTER_NOISE: i32 = 5;
+
+pub struct Noise {
+    pub x: i32,
+    pub y: i32,
+    pub radius: i32,
+}
+
+/// Noises made since monsters last acted, each heard up to its radius away
+#[derive(Default)]
+pub struct NoiseEvents {
+    pub noises: Vec<Noise>,
+}
+
+impl NoiseEvents {
+    pub fn emit(&mut self, x: i32, y: i32, radius: i32) {
+        self.noises.push(Noise{ x, y, radius });
+    }
+}
+
+/// How loud each tile was when noise last spread, and where the loudest noise came from
+#[derive(Default)]
+pub struct NoiseField {
+    pub volume: Vec<f32>,
+    pub source: Vec<Option<Point>>,
+    pub show: bool,
+}
+
+impl NoiseField {
+    pub fn clear(&mut self, size: usize) {
+        self.volume = vec![0.0; size];
+        self.source = vec![None; size];
+    }
+
+    /// Spreads a noise over the map, keeping the loudest noise heard on each tile
+    pub fn add(&mut self, map: &Map, noise: &Noise) {
+        for (idx, distance) in propagate(map, noise.x, noise.y, noise.radius) {
+            let volume = noise.radius as f32 - distance;
+            if volume > self.volume[idx] {
+                self.volume[idx] = volume;
+                self.source[idx] = Some(Point::new(noise.x, noise.y));
+            }
+        }
+    }
+}
+
+/// Forgets queued noises and the last noise field, so nothing heard on an old map carries over to a new one
+pub fn reset(ecs: &mut World) {
+    let size = {
+        let map = ecs.fetch::<Map>();
+        (map.width * map.height) as usize
+    };
+    ecs.fetch_mut::<NoiseEvents>().noises.clear();
+    ecs.fetch_mut::<NoiseField>().clear(size);
+}
+
+/// Spreads a noise out from its source around walls, returning the pathing distance to every tile it reaches
+pub fn propagate(map: &Map, x: i32, y: i32, radius: i32) -> Vec<(usize, f32)> {
+    // Distances are kept in hundredths of a tile so they can be ordered on the heap
+    let max_cost = radius * 100;
+    let mut cost = vec![i32::MAX; (map.width * map.height) as usize];
+    let mut open = BinaryHeap::new();
+    let start = map.xy_idx(x, y);
+    cost[start] = 0;
+    open.push(Reverse((0, start)));
+
+    let mut reached = Vec::new();
+    while let Some(Reverse((current_cost, idx))) = open.pop() {
+        if current_cost > cost[idx] { continue; }
+        reached.push((idx, current_cost as f32 / 100.0));
+
+        let cx = idx as i32 % map.width;
+        let cy = idx as i32 / map.width;
+        for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)].iter() {
+            let (nx, ny) = (cx + dx, cy + dy);
+            if nx < 0 || nx >= map.width || ny < 0 || ny >= map.height { continue; }
+            let next = map.xy_idx(nx, ny);
+            if map.tiles[next] == TileType::Wall { continue; }
+
+            let step = if *dx != 0 && *dy != 0 { 145 } else { 100 };
+            let next_cost = current_cost + step;
+            if next_cost <= max_cost && next_cost < cost[next] {
+                cost[next] = next_cost;
+                open.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    reached
+}
+
+/// Debug overlay shading every tile by how loud it was when noise last spread
+pub fn draw_noise_field(ecs: &World, ctx: &mut Rltk) {
+    let field = ecs.fetch::<NoiseField>();
+    if !field.show { return; }
+
+    let map = ecs.fetch::<Map>();
+    for (idx, volume) in field.volume.iter().enumerate() {
+        if *volume <= 0.0 { continue; }
+        let intensity = f32::min(1.0, volume / ATTACK_NOISE as f32);
+        let x = idx as i32 % map.width;
+        let y = idx as i32 / map.width;
+        ctx.set_bg(x, y, RGB::from_f32(0.1 + 0.5 * intensity, 0.0, 0.2 + 0.6 * intensity));
+    }
+}
\ No newline at end of file
@@ -1,23 +1,62 @@
 use specs::prelude::*;
 use super::{
-    Position, map, Map, RunState,
-    creature, item,
+    Position, map, noise, Map, RunState, Gamelog,
+    creature, item, orb, status,
     deck::Deck
 };
 
 use rltk::{Rltk, VirtualKeyCode, Point};
 use std::cmp::{max, min};
 
+const SEARCH_RADIUS: i32 = 3;
+
 pub fn move_player(delta_x: i32, delta_y: i32, ecs: &mut World) -> RunState {
     let mut positions = ecs.write_storage::<Position>();
     let mut player_pos = ecs.write_resource::<Point>();
     let mut players = ecs.write_storage::<creature::Player>();
     let mut viewsheds = ecs.write_storage::<creature::Viewshed>();
-    let map = ecs.fetch::<Map>();
+    let mut map = ecs.fetch_mut::<Map>();
+    let status_entangled = ecs.read_storage::<status::Entangled>();
+    let mut status_slowed = ecs.write_storage::<status::Slowed>();
+    let mut entity_moved = ecs.write_storage::<creature::EntityMoved>();
+    let entities = ecs.entities();
+
+    let mut opened_door = false;
+    for (entity, _, pos, viewshed, entangled, slowed) in (&entities, &mut players, &mut positions, &mut viewsheds,
+            status_entangled.maybe(), (&mut status_slowed).maybe()).join() {
+        // Refuse to move while held in place, explaining why; a slowed move still uses up the turn
+        if let Some(reason) = status::movement_blocked(entangled, slowed) {
+            ecs.fetch_mut::<Gamelog>().push(format!("You are {} and cannot move this turn.", reason));
+            if entangled.is_some() {
+                return RunState::AwaitingInput;
+            }
+            return RunState::EndTurn{ player_end_turn: true };
+        }
 
-    for (_, pos, viewshed) in (&mut players, &mut positions, &mut viewsheds).join() {
         let destination_idx = map.xy_idx(pos.x + delta_x, pos.y + delta_y);
 
+        // Walking into a door opens it, which takes up the move
+        match map.tiles[destination_idx] {
+            map::TileType::ClosedDoor => {
+                map.open_door(destination_idx);
+                ecs.fetch_mut::<Gamelog>().push("You open the door.".to_string());
+                opened_door = true;
+                break;
+            }
+            map::TileType::LockedDoor => {
+                if map.keys < 1 {
+                    ecs.fetch_mut::<Gamelog>().push("The door is locked.".to_string());
+                    return RunState::AwaitingInput;
+                }
+                map.keys -= 1;
+                map.open_door(destination_idx);
+                ecs.fetch_mut::<Gamelog>().push("You unlock the door with a key.".to_string());
+                opened_door = true;
+                break;
+            }
+            _ => {}
+        }
+
         // Move to the tile if it is not blocked and end player turn
         if !map.blocked[destination_idx] {
             pos.x = min(map.width - 1, max(0, pos.x + delta_x));
@@ -26,14 +65,35 @@ pub fn move_player(delta_x: i32, delta_y: i32, ecs: &mut World) -> RunState {
             player_pos.y = pos.y;
 
             viewshed.dirty = true;
+            entity_moved.insert(entity, creature::EntityMoved{}).expect("Unable to insert creature::EntityMoved");
 
             return RunState::EndTurn{ player_end_turn: true };
         }
     }
 
+    // Opened doors change what everyone can see
+    if opened_door {
+        for viewshed in (&mut viewsheds).join() {
+            viewshed.dirty = true;
+        }
+        return RunState::EndTurn{ player_end_turn: true };
+    }
+
     RunState::AwaitingInput
 }
 
+// Searching the nearby tiles for traps takes up the player's move
+fn search(ecs: &mut World) -> RunState {
+    let player_pos = *ecs.fetch::<Point>();
+    let found = ecs.fetch_mut::<Map>().reveal_traps(player_pos, SEARCH_RADIUS);
+    let mut log = ecs.fetch_mut::<Gamelog>();
+    match found {
+        0 => log.push("You search the area but find nothing.".to_string()),
+        n => log.push(format!("You search the area and find {} trap(s).", n)),
+    }
+    RunState::EndTurn{ player_end_turn: true }
+}
+
 fn go_next_level(ecs: &mut World) -> RunState {
     let player_pos = ecs.fetch::<Point>();
     let map = ecs.fetch::<Map>();
@@ -88,9 +148,21 @@ fn redraw_hand(ecs: &mut World) {
 fn restore_energy(ecs: &mut World) {
     let player_entity = ecs.fetch::<Entity>();
     let mut player = ecs.write_storage::<creature::Player>();
+    let orb_slots = ecs.read_storage::<orb::OrbSlots>();
 
     if let Some(player_energy) = player.get_mut(*player_entity) {
         player_energy.energy = player_energy.max_energy;
+
+        // Plasma orbs add their passive energy on top of the refill
+        if let Some(slots) = orb_slots.get(*player_entity) {
+            for o in slots.orbs.iter() {
+                let energy = o.energy(false);
+                if energy > 0 {
+                    player_energy.energy += energy;
+                    ecs.fetch_mut::<Gamelog>().push(format!("{} orb grants {} energy.", o.to_name(), energy));
+                }
+            }
+        }
     }
 }
 
@@ -125,6 +197,13 @@ pub fn player_input(ecs: &mut World, ctx: &mut Rltk) -> RunState {
             VirtualKeyCode::Key0 => return RunState::ShowHand{ selection: 9 },
             VirtualKeyCode::Space => end_turn(ecs),
             VirtualKeyCode::G => get_item(ecs),
+            VirtualKeyCode::F => return search(ecs),
+            VirtualKeyCode::N => {
+                // Toggle the debug overlay of the last noise field
+                let mut field = ecs.fetch_mut::<noise::NoiseField>();
+                field.show = !field.show;
+                return RunState::AwaitingInput;
+            }
             VirtualKeyCode::Escape => return RunState::SaveGame,
             _ => { return RunState::AwaitingInput }
         }
@@ -4,16 +4,22 @@ use specs::error::NoError;
 use specs_derive::{Component, ConvertSaveload};
 use serde::{Serialize, Deserialize};
 
-use std::fs;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use std::env;
 use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read};
 use std::path::Path;
 
-const SAVE_PATH: &str = "./save.json";
+const SAVE_PATH: &str = "./save.mpk.gz";
+const SAVE_PATH_VAR: &str = "SPIRE_ROGUE_SAVE";
 
 use super::{
     util::EntityVec,
-    Name, Position, Renderable, Map, deck,
-    creature, effects, item, status
+    Name, Position, Renderable, Map, Gold, deck, noise,
+    creature, effects, item, orb, passive, relic, stance, status
 };
 
 pub struct SerializeMe;
@@ -28,8 +34,16 @@ pub struct SerializableDeck {
 #[derive(Component, Serialize, Deserialize, Clone)]
 pub struct SerializableResources {
     pub map: Map,
+    #[serde(default)]
+    pub gold: Gold,
+    // Saves without a version predate the current list of saved components
+    #[serde(default)]
+    pub version: u32,
 }
 
+// Bumped whenever saved components are added, removed or reordered, since they are read back in order
+const SAVE_VERSION: u32 = 1;
+
 macro_rules! serialize_individually {
     ($ecs:expr, $ser:expr, $data:expr, $( $type:ty),*) => {
         $(
@@ -54,21 +68,89 @@ macro_rules! deserialize_individually {
             &mut $data.2,
             &mut $de,
         )
-        .unwrap();
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
         )*
     };
 }
 
+macro_rules! register_individually {
+    ($ecs:expr, $de:expr, $data:expr, $( $type:ty),*) => {
+        $(
+        $ecs.register::<$type>();
+        )*
+    };
+}
+
+// Every component that is written to and read from a save file
+macro_rules! saved_components {
+    ($individually:ident, $ecs:expr, $serde:expr, $data:expr) => {
+        $individually!(
+            $ecs, $serde, $data, SerializableResources, SerializableDeck, Name, Position, Renderable,
+            creature::Player, creature::Monster, creature::BlocksTile, creature::Viewshed,
+            creature::SufferDamage, creature::EntityMoved, creature::PerformAction, creature::PickupItem, creature::Attack,
+            creature::Intent, creature::AttackCycle, creature::InCombat, creature::Boss, creature::Elite, creature::EliteGroup, creature::Gremlin,
+            creature::Stationary, creature::Enrage, creature::Asleep, creature::Splits, creature::ModeShift,
+            creature::Thief, creature::Fleeing, creature::Behaviour, creature::Awareness, creature::Stealth,
+            item::Item, item::Potion, item::InBackpack, item::Card, item::CardKind, item::Ethereal, item::Fragile,
+            item::Unplayable, item::GoldPile, item::Key, item::Targeted, item::TargetsAllies, item::Projectile, item::AreaOfEffect,
+            effects::DealDamage, effects::GainBlock, effects::DiscardCard, effects::DrawCard, effects::GainCard,
+            effects::BuffStrength, effects::BuffDexterity, effects::DebuffStrength, effects::DebuffDexterity,
+            effects::Teleport, effects::LoseHp, effects::GainEnergy, effects::HealAfterCombat,
+            effects::GainStealth, effects::GrantBlock, effects::GrantStrength, effects::StealGold, effects::Flee,
+            effects::Push, effects::Pull, effects::Swap, effects::CreateTerrain, effects::RevealTraps,
+            effects::ChannelOrb, effects::EvokeOrb, effects::GainOrbSlots, effects::ChangeStance,
+            effects::GainPassive, status::Weak, status::Vulnerable, status::Frail, status::Poison,
+            status::Entangled, status::Rooted, status::Slowed,
+            relic::Relics, orb::OrbSlots, stance::InStance, passive::Passives
+        )
+    };
+}
+
+/// Save file encodings, chosen by the extension of the save path
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum SaveFormat {
+    Json,
+    MessagePack,
+    CompressedMessagePack,
+}
+
+impl SaveFormat {
+    pub fn from_path(path: &str) -> SaveFormat {
+        if path.ends_with(".json") {
+            SaveFormat::Json
+        } else if path.ends_with(".gz") {
+            SaveFormat::CompressedMessagePack
+        } else {
+            SaveFormat::MessagePack
+        }
+    }
+}
+
+/// Path of the save file, which can be overridden with the SPIRE_ROGUE_SAVE environment variable
+pub fn save_path() -> String {
+    env::var(SAVE_PATH_VAR).unwrap_or_else(|_| SAVE_PATH.to_string())
+}
+
 pub fn save_exists() -> bool {
-    Path::new(SAVE_PATH).exists()
+    Path::new(&save_path()).exists()
 }
 
-pub fn save_game(ecs: &mut World) {
+pub fn save_game(ecs: &mut World) -> io::Result<()> {
+    save_game_to(ecs, &save_path())
+}
+
+pub fn save_game_to(ecs: &mut World, path: &str) -> io::Result<()> {
+    // Open the destination before creating any helper entities, so a bad path leaves the world untouched
+    let writer = BufWriter::new(File::create(path)?);
+
     // Helper to serialize various resources
     let map_copy = ecs.get_mut::<Map>().unwrap().clone();
+    let gold_copy = *ecs.fetch::<Gold>();
     let resource_helper = ecs.create_entity()
                         .with(SerializableResources{
                             map: map_copy,
+                            gold: gold_copy,
+                            version: SAVE_VERSION,
                         })
                         .marked::<SimpleMarker<SerializeMe>>()
                         .build();
@@ -86,27 +168,101 @@ pub fn save_game(ecs: &mut World) {
                         .build();
 
     // Serialize and save data
-    {
+    let result = {
         let data = (ecs.entities(), ecs.read_storage::<SimpleMarker<SerializeMe>>());
 
-        let writer = File::create(SAVE_PATH).unwrap();
-        let mut serializer = serde_json::Serializer::new(writer);
-        serialize_individually!(
-            ecs, serializer, data, SerializableResources, SerializableDeck, Name, Position, Renderable,
-            creature::Player, creature::Monster, creature::BlocksTile, creature::Viewshed, creature::SufferDamage,
-            creature::PerformAction, creature::PickupItem, creature::Attack, creature::Intent, creature::AttackCycle,
-            item::Item, item::Potion, item::InBackpack, item::Card, item::Ethereal, item::Targeted, item::AreaOfEffect,
-            effects::DealDamage, effects::GainBlock, effects::DiscardCard, effects::DrawCard, effects::GainCard,
-            effects::BuffStrength, effects::BuffDexterity, effects::Teleport,
-            status::Weak, status::Vulnerable, status::Frail, status::Poison
-        );
-    }
+        match SaveFormat::from_path(path) {
+            SaveFormat::Json => {
+                let mut serializer = serde_json::Serializer::new(writer);
+                saved_components!(serialize_individually, ecs, serializer, data);
+                Ok(())
+            }
+            SaveFormat::MessagePack => {
+                let mut serializer = rmp_serde::Serializer::new(writer);
+                saved_components!(serialize_individually, ecs, serializer, data);
+                Ok(())
+            }
+            SaveFormat::CompressedMessagePack => {
+                let mut serializer = rmp_serde::Serializer::new(GzEncoder::new(writer, Compression::default()));
+                saved_components!(serialize_individually, ecs, serializer, data);
+                serializer.into_inner().finish().map(|_| ())
+            }
+        }
+    };
 
     ecs.delete_entity(resource_helper).expect("Crashed on cleanup");
     ecs.delete_entity(deck_helper).expect("Crashed on cleanup");
+    result
+}
+
+/// Reads a whole save file into memory, decompressing it if needed
+fn read_save(path: &str) -> io::Result<Vec<u8>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut bytes = Vec::new();
+    match SaveFormat::from_path(path) {
+        SaveFormat::CompressedMessagePack => GzDecoder::new(reader).read_to_end(&mut bytes)?,
+        _ => reader.read_to_end(&mut bytes)?,
+    };
+    if bytes.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{} is empty", path)));
+    }
+    Ok(bytes)
+}
+
+// Reads every saved component from the save into the world
+fn deserialize_save(ecs: &mut World, bytes: &[u8], format: SaveFormat) -> io::Result<()> {
+    let mut data = (&mut ecs.entities(), &mut ecs.write_storage::<SimpleMarker<SerializeMe>>(), &mut ecs.write_resource::<SimpleMarkerAllocator<SerializeMe>>());
+    match format {
+        SaveFormat::Json => {
+            let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+            saved_components!(deserialize_individually, ecs, deserializer, data);
+        }
+        // Compressed saves were already inflated by read_save
+        SaveFormat::MessagePack | SaveFormat::CompressedMessagePack => {
+            let mut deserializer = rmp_serde::Deserializer::new(bytes);
+            saved_components!(deserialize_individually, ecs, deserializer, data);
+        }
+    }
+    Ok(())
+}
+
+// Loads the save into an empty world of its own, making sure it is complete and from this version of the game
+fn check_save(bytes: &[u8], format: SaveFormat) -> io::Result<()> {
+    let mut scratch = World::new();
+    saved_components!(register_individually, scratch, (), ());
+    scratch.register::<SimpleMarker<SerializeMe>>();
+    scratch.insert(SimpleMarkerAllocator::<SerializeMe>::new());
+    deserialize_save(&mut scratch, bytes, format)?;
+
+    let problem = match (&scratch.read_storage::<SerializableResources>()).join().next() {
+        None => Some("save has no resources".to_string()),
+        Some(r) if r.version != SAVE_VERSION => {
+            Some(format!("save is from an unsupported version ({}, expected {})", r.version, SAVE_VERSION))
+        }
+        Some(_) if (&scratch.read_storage::<SerializableDeck>()).join().next().is_none() => {
+            Some("save has no deck".to_string())
+        }
+        Some(_) if (&scratch.read_storage::<creature::Player>(), &scratch.read_storage::<Position>()).join().next().is_none() => {
+            Some("save has no player".to_string())
+        }
+        Some(_) => None,
+    };
+    match problem {
+        Some(reason) => Err(io::Error::new(io::ErrorKind::InvalidData, reason)),
+        None => Ok(()),
+    }
 }
 
-pub fn load_game(ecs: &mut World) {
+pub fn load_game(ecs: &mut World) -> io::Result<()> {
+    load_game_from(ecs, &save_path())
+}
+
+pub fn load_game_from(ecs: &mut World, path: &str) -> io::Result<()> {
+    // Read and check the whole save before touching the world, so a missing or corrupt file keeps the current game
+    let bytes = read_save(path)?;
+    let format = SaveFormat::from_path(path);
+    check_save(&bytes, format)?;
+
     // Delete everything
     {
         let mut to_delete = Vec::new();
@@ -118,20 +274,7 @@ pub fn load_game(ecs: &mut World) {
         }
     }
 
-    {
-        let save_data = fs::read_to_string(SAVE_PATH).unwrap();
-        let mut deserializer = serde_json::Deserializer::from_str(&save_data);
-        let mut data = (&mut ecs.entities(), &mut ecs.write_storage::<SimpleMarker<SerializeMe>>(), &mut ecs.write_resource::<SimpleMarkerAllocator<SerializeMe>>());
-        deserialize_individually!(
-            ecs, deserializer, data, SerializableResources, SerializableDeck, Name, Position, Renderable,
-            creature::Player, creature::Monster, creature::BlocksTile, creature::Viewshed, creature::SufferDamage,
-            creature::PerformAction, creature::PickupItem, creature::Attack, creature::Intent, creature::AttackCycle,
-            item::Item, item::Potion, item::InBackpack, item::Card, item::Ethereal, item::Targeted, item::AreaOfEffect,
-            effects::DealDamage, effects::GainBlock, effects::DiscardCard, effects::DrawCard, effects::GainCard,
-            effects::BuffStrength, effects::BuffDexterity, effects::Teleport,
-            status::Weak, status::Vulnerable, status::Frail, status::Poison
-        );
-    }
+    deserialize_save(ecs, &bytes, format)?;
 
     let mut to_delete: [Option<Entity>; 2] = [None, None];
     {
@@ -145,7 +288,8 @@ pub fn load_game(ecs: &mut World) {
         for (e, r) in (&entities, &resource_helper).join() {
             let mut map = ecs.write_resource::<Map>();
             *map = r.map.clone();
-            map.tile_content = vec![Vec::new(); super::map::MAPSIZE];
+            map.rebuild_derived();
+            *ecs.write_resource::<Gold>() = r.gold;
             to_delete[0] = Some(e);
         }
 
@@ -170,4 +314,18 @@ pub fn load_game(ecs: &mut World) {
     for del in to_delete.iter() {
         ecs.delete_entity(del.unwrap()).expect("Crashed on cleanup");
     }
+    noise::reset(ecs);
+
+    // Viewsheds are recalculated on the first turn after loading
+    let mut viewsheds = ecs.write_storage::<creature::Viewshed>();
+    for viewshed in (&mut viewsheds).join() {
+        viewshed.dirty = true;
+    }
+    Ok(())
+}
+
+/// Rewrites a save file in the format given by the extension of the destination path
+pub fn convert_save(ecs: &mut World, from: &str, to: &str) -> io::Result<()> {
+    load_game_from(ecs, from)?;
+    save_game_to(ecs, to)
 }
\ No newline at end of file
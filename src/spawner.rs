@@ -1,32 +1,45 @@
 use specs::prelude::*;
 use specs::saveload::{SimpleMarker, MarkedBuilder};
 use rltk::{RGB, RandomNumberGenerator};
+use std::collections::VecDeque;
 
 use super::{
     Name, Position, Renderable, saveload,
-    creature, effects, cards, item, monsters,
-    util::Rect, map::MAPWIDTH,
+    creature, effects, item, monsters, orb, relic, stance,
+    Gamelog, act, character::{self, Character}, util::Rect, map::{Map, TileType, TileEffectType, MAPWIDTH},
 };
 
-pub fn player(ecs: &mut World, x: i32, y: i32) -> Entity {
-    ecs.create_entity()
-        .with(Name{ name: "Silent".to_string() })
+pub fn player(ecs: &mut World, x: i32, y: i32, character: Character) -> Entity {
+    let max_hp = character.max_hp();
+    let max_energy = character.max_energy();
+
+    let mut player = ecs.create_entity()
+        .with(Name{ name: character.to_name() })
         .with(Position{ x, y })
         .with(Renderable{
-            glyph: rltk::to_cp437('@'),
-            fg: RGB::from_f32(0.1, 0.8, 0.1),
+            glyph: character.glyph(),
+            fg: character.color(),
             bg: RGB::named(rltk::BLACK),
             render_order: 0,
         })
         .with(creature::Creature{})
-        .with(creature::Player{ max_energy: 3, energy: 3 })
-        .with(creature::CombatStats{ max_hp: 70, hp: 70, block: 0,
+        .with(creature::Player{ max_energy, energy: max_energy, character })
+        .with(creature::CombatStats{ max_hp, hp: max_hp, block: 0,
             base_strength: 0, strength: 0,
             base_dexterity: 0, dexterity: 0
         })
         .with(creature::Viewshed{ visible_tiles: Vec::new(), range: 8, dirty: true })
-        .marked::<SimpleMarker<saveload::SerializeMe>>()
-        .build()
+        .with(creature::Stealth{ base: character.stealth(), bonus: 0 })
+        .with(relic::Relics{ relics: vec![character.starter_relic()] });
+
+    if character.orb_slots() > 0 {
+        player = player.with(orb::OrbSlots::new(character.orb_slots()));
+    }
+    if character.uses_stances() {
+        player = player.with(stance::InStance{ stance: stance::Stance::Neutral });
+    }
+
+    player.marked::<SimpleMarker<saveload::SerializeMe>>().build()
 }
 
 fn potion_block(ecs: &mut World, x: i32, y: i32) {
@@ -59,6 +72,7 @@ fn potion_fire(ecs: &mut World, x: i32, y: i32) {
         .with(item::Item{})
         .with(item::Potion{})
         .with(item::Targeted{ range: 3 })
+        .with(item::Projectile{})
         .with(effects::DealDamage{ amount: 20 })
         .marked::<SimpleMarker<saveload::SerializeMe>>()
         .build();
@@ -77,71 +91,423 @@ fn potion_explosive(ecs: &mut World, x: i32, y: i32) {
         .with(item::Item{})
         .with(item::Potion{})
         .with(item::Targeted{ range: 5 })
-        .with(item::AreaOfEffect{ radius: 1 })
+        .with(item::Projectile{})
+        .with(item::AreaOfEffect{ radius: 1, shape: item::AoeShape::Circle, friendly_fire: true })
         .with(effects::DealDamage{ amount: 10 })
         .marked::<SimpleMarker<saveload::SerializeMe>>()
         .build();
 }
 
+fn potion_smoke_bomb(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position{ x, y })
+        .with(Renderable{
+            glyph: rltk::to_cp437('!'),
+            fg: RGB::named(rltk::GRAY),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name{ name: "Smoke Bomb".to_string() })
+        .with(item::Item{})
+        .with(item::Potion{})
+        .with(item::AreaOfEffect{ radius: 1, shape: item::AoeShape::Burst, friendly_fire: false })
+        .with(effects::GainStealth{ amount: 6 })
+        .with(effects::CreateTerrain{ terrain: TileEffectType::Smoke, turns: 4 })
+        .marked::<SimpleMarker<saveload::SerializeMe>>()
+        .build();
+}
+
+fn potion_poison_gas(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position{ x, y })
+        .with(Renderable{
+            glyph: rltk::to_cp437('*'),
+            fg: RGB::named(rltk::CHARTREUSE),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name{ name: "Poison Gas Potion".to_string() })
+        .with(item::Item{})
+        .with(item::Potion{})
+        .with(item::Targeted{ range: 4 })
+        .with(item::Projectile{})
+        .with(item::AreaOfEffect{ radius: 1, shape: item::AoeShape::Circle, friendly_fire: true })
+        .with(effects::CreateTerrain{ terrain: TileEffectType::PoisonGas, turns: 4 })
+        .marked::<SimpleMarker<saveload::SerializeMe>>()
+        .build();
+}
+
+fn potion_oil(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position{ x, y })
+        .with(Renderable{
+            glyph: rltk::to_cp437('*'),
+            fg: RGB::named(rltk::SADDLEBROWN),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name{ name: "Oil Flask".to_string() })
+        .with(item::Item{})
+        .with(item::Potion{})
+        .with(item::Targeted{ range: 4 })
+        .with(item::Projectile{})
+        .with(item::AreaOfEffect{ radius: 1, shape: item::AoeShape::Circle, friendly_fire: true })
+        .with(effects::CreateTerrain{ terrain: TileEffectType::Oil, turns: 8 })
+        .marked::<SimpleMarker<saveload::SerializeMe>>()
+        .build();
+}
+
 pub fn random_potion(ecs: &mut World, x: i32, y: i32) {
     let roll: i32;
     {
         let mut rng = ecs.write_resource::<RandomNumberGenerator>();
-        roll = rng.roll_dice(1, 3);
+        roll = rng.roll_dice(1, 6);
     }
 
     match roll {
         1 => { potion_block(ecs, x, y) }
         2 => { potion_explosive(ecs, x, y) }
+        3 => { potion_smoke_bomb(ecs, x, y) }
+        4 => { potion_poison_gas(ecs, x, y) }
+        5 => { potion_oil(ecs, x, y) }
         _ => { potion_fire(ecs, x, y) }
     }
 }
 
+fn gold_pile(ecs: &mut World, x: i32, y: i32, amount: i32) {
+    ecs.create_entity()
+        .with(Position{ x, y })
+        .with(Renderable{
+            glyph: rltk::to_cp437('$'),
+            fg: RGB::named(rltk::GOLD),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name{ name: format!("{} Gold", amount) })
+        .with(item::Item{})
+        .with(item::GoldPile{ amount })
+        .marked::<SimpleMarker<saveload::SerializeMe>>()
+        .build();
+}
+
+fn key(ecs: &mut World, x: i32, y: i32) {
+    ecs.create_entity()
+        .with(Position{ x, y })
+        .with(Renderable{
+            glyph: rltk::to_cp437('¬'),
+            fg: RGB::named(rltk::GOLD),
+            bg: RGB::named(rltk::BLACK),
+            render_order: 2,
+        })
+        .with(Name{ name: "Key".to_string() })
+        .with(item::Item{})
+        .with(item::Key{})
+        .marked::<SimpleMarker<saveload::SerializeMe>>()
+        .build();
+}
+
+/// Positions of fallen elites whose rewards have yet to be spawned,
+/// and gold dropped by fallen thieves
+#[derive(Default)]
+pub struct RewardQueue {
+    pub positions: Vec<(i32, i32)>,
+    pub gold: Vec<(i32, i32, i32)>,
+}
+
+/// Spawns dropped gold, and a potion and two cards around every fallen elite,
+/// along with a key if the floor has a locked door
+pub fn spawn_queued_rewards(ecs: &mut World) {
+    let gold: Vec<(i32, i32, i32)> = ecs.write_resource::<RewardQueue>().gold.drain(..).collect();
+    for (x, y, amount) in gold {
+        gold_pile(ecs, x, y, amount);
+    }
+
+    let queued: Vec<(i32, i32)> = ecs.write_resource::<RewardQueue>().positions.drain(..).collect();
+    if queued.is_empty() { return; }
+
+    let character = character::player_character(ecs);
+    let locked_door = ecs.fetch::<Map>().tiles.iter().any(|tile| *tile == TileType::LockedDoor);
+    let num_drops = if locked_door { 4 } else { 3 };
+    for (x, y) in queued {
+        // Spread the rewards over the open tiles around the elite
+        let mut drop_points: Vec<(i32, i32)> = Vec::new();
+        {
+            let map = ecs.fetch::<Map>();
+            for (dx, dy) in [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (-1, -1), (1, -1), (-1, 1)].iter() {
+                let idx = map.xy_idx(x + dx, y + dy);
+                if map.tiles[idx] == TileType::Floor { drop_points.push((x + dx, y + dy)); }
+                if drop_points.len() == num_drops { break; }
+            }
+        }
+        while drop_points.len() < num_drops { drop_points.push((x, y)); }
+
+        random_potion(ecs, drop_points[0].0, drop_points[0].1);
+        character.random_card(ecs, drop_points[1].0, drop_points[1].1);
+        character.random_card(ecs, drop_points[2].0, drop_points[2].1);
+        if locked_door {
+            key(ecs, drop_points[3].0, drop_points[3].1);
+        }
+    }
+    ecs.fetch_mut::<Gamelog>().push("The elite leaves its spoils behind.".to_string());
+}
+
+/// Monsters that have used their turn to split and are waiting to be replaced
+#[derive(Default)]
+pub struct SplitQueue {
+    pub splitting: Vec<Entity>,
+}
+
+/// Replaces every splitting monster with its halves, each keeping the health the monster had left
+pub fn spawn_queued_splits(ecs: &mut World) {
+    let queued: Vec<Entity> = ecs.write_resource::<SplitQueue>().splitting.drain(..).collect();
+
+    for parent in queued {
+        let (x, y, hp, into, is_boss, intent) = {
+            let positions = ecs.read_storage::<Position>();
+            let combat_stats = ecs.read_storage::<creature::CombatStats>();
+            let splits = ecs.read_storage::<creature::Splits>();
+            let intents = ecs.read_storage::<creature::Intent>();
+            let pos = match positions.get(parent) { Some(p) => p, None => continue };
+            let hp = match combat_stats.get(parent) { Some(s) => s.hp, None => continue };
+            let into = match splits.get(parent) { Some(s) => s.into.clone(), None => continue };
+            let is_boss = ecs.read_storage::<creature::Boss>().get(parent).is_some();
+            (pos.x, pos.y, hp, into, is_boss, intents.get(parent).map(|i| i.intent))
+        };
+
+        if let Some(name) = ecs.read_storage::<Name>().get(parent) {
+            ecs.fetch_mut::<Gamelog>().push(format!("{} splits!", name.name));
+        }
+
+        // The halves take the parent's tile first, then the nearest free tiles around it
+        let mut spawn_points = {
+            let mut map = ecs.fetch_mut::<Map>();
+            let idx = map.xy_idx(x, y);
+            map.blocked[idx] = false;
+            let points = free_tiles_around(&map, x, y, into.len());
+            for (sx, sy) in points.iter() {
+                let idx = map.xy_idx(*sx, *sy);
+                map.blocked[idx] = true;
+            }
+            points
+        };
+        // Halves with nowhere else to go share the parent's tile rather than being lost
+        while spawn_points.len() < into.len() { spawn_points.push((x, y)); }
+
+        ecs.delete_entity(parent).expect("Unable to delete splitting monster");
+        if let Some(intent) = intent {
+            ecs.delete_entity(intent).expect("Unable to delete splitting monster's intent");
+        }
+
+        for (mob, (sx, sy)) in into.into_iter().zip(spawn_points.into_iter()) {
+            let child = mob.spawn(ecs, sx, sy);
+            if let Some(stats) = ecs.write_storage::<creature::CombatStats>().get_mut(child) {
+                stats.max_hp = hp;
+                stats.hp = hp;
+            }
+            if is_boss {
+                ecs.write_storage::<creature::Boss>().insert(child, creature::Boss{}).expect("Unable to insert creature::Boss");
+            }
+        }
+    }
+}
+
+/// Fills every room of a floor except the player's starting room, marking the elite room on the map.
+/// Floors with an elite may also lock a vault room that the elite's key opens.
+pub fn spawn_floor(ecs: &mut World, map: &mut Map) {
+    if act::is_boss_floor(map.depth) {
+        spawn_boss_room(ecs, &map.rooms[map.rooms.len() - 1], map.depth);
+        return;
+    }
+
+    // Every floor after the first of an act has an elite room
+    let mut elite_room = None;
+    if act::floor_in_act(map.depth) > 1 && map.rooms.len() > 2 {
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        elite_room = Some(rng.range(1, map.rooms.len() as i32) as usize);
+    }
+    let mut vault = None;
+    if let Some(i) = elite_room {
+        let room = map.rooms[i];
+        map.elite_rooms.push(room);
+
+        // The vault is never the starting room or the elite's own room, and locking it
+        // checks that the stairs can still be reached
+        let candidates: Vec<usize> = (1 .. map.rooms.len()).filter(|r| *r != i).collect();
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        vault = map.lock_vault(&mut rng, &candidates);
+    }
+
+    let map: &Map = map;
+    for (i, room) in map.rooms.iter().enumerate().skip(1) {
+        if elite_room == Some(i) {
+            spawn_elite_room(ecs, map, i);
+        } else if vault == Some(i) {
+            spawn_vault(ecs, map, room);
+        } else {
+            spawn_room(ecs, map, room);
+        }
+    }
+}
+
 /// Fills a room with monsters and items
-pub fn spawn_room(ecs: &mut World, room: &Rect, map_depth: i32) {
+pub fn spawn_room(ecs: &mut World, map: &Map, room: &Rect) {
     // Pick an encounter
     let encounter: monsters::Encounters;
     {
-        let encounter_table = monsters::spawn_table(map_depth);
+        let encounter_table = monsters::spawn_table(map.depth);
         let mut rng = ecs.write_resource::<RandomNumberGenerator>();
         encounter = encounter_table.roll(&mut rng).unwrap();
     }
+    spawn_encounter(ecs, map, room, encounter);
+    spawn_items(ecs, map, room);
+}
+
+/// Fills a room with an elite encounter and items. The elites share one reward, grouped by their room.
+pub fn spawn_elite_room(ecs: &mut World, map: &Map, room_idx: usize) {
+    let room = &map.rooms[room_idx];
+    let encounter: monsters::Encounters;
     {
-        let entry = encounter.spawn(ecs);
+        let elite_table = monsters::elite_table(map.depth);
         let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        encounter = elite_table.roll(&mut rng).unwrap();
+    }
+    let elites = spawn_encounter(ecs, map, room, encounter);
+    {
+        let mut groups = ecs.write_storage::<creature::EliteGroup>();
+        for elite in elites {
+            groups.insert(elite, creature::EliteGroup{ room: room_idx }).expect("Unable to insert creature::EliteGroup");
+        }
+    }
+    spawn_items(ecs, map, room);
+}
 
-        // Decide spawn points
-        let mut spawn_points: Vec<usize> = Vec::new();
-        for _ in 0 .. entry.len() {
-            let mut added = false;
-            let mut tries = 0;
-            while !added && tries < 20 {
-                let x = (room.x1 + rng.roll_dice(1, i32::abs(room.x2 - room.x1))) as usize;
-                let y = (room.y1 + rng.roll_dice(1, i32::abs(room.y2 - room.y1))) as usize;
-                let idx = (y * MAPWIDTH) + x;
-                if !spawn_points.contains(&idx) {
-                    spawn_points.push(idx);
-                    added = true;
-                } else {
-                    tries += 1;
-                }
+/// Stocks a locked vault with gold, a potion and cards instead of monsters
+pub fn spawn_vault(ecs: &mut World, map: &Map, room: &Rect) {
+    let mut spawn_points: Vec<usize> = Vec::new();
+    let gold;
+    {
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        gold = rng.range(25, 50);
+        let mut tries = 0;
+        while spawn_points.len() < 4 && tries < 20 {
+            let x = (room.x1 + rng.roll_dice(1, i32::abs(room.x2 - room.x1))) as usize;
+            let y = (room.y1 + rng.roll_dice(1, i32::abs(room.y2 - room.y1))) as usize;
+            let idx = (y * MAPWIDTH) + x;
+            if !spawn_points.contains(&idx) && is_safe_spawn(map, idx) {
+                spawn_points.push(idx);
+            } else {
+                tries += 1;
             }
         }
+    }
 
-        // Set spawn points
-        let mut positions = ecs.write_storage::<Position>();
-        for (i, idx) in spawn_points.iter().enumerate() {
-            let x = (*idx % MAPWIDTH) as i32;
-            let y = (*idx / MAPWIDTH) as i32;
-            let ent = entry[i];
+    // Cramped vaults hold fewer rewards, dropping the cards first
+    let mut points = spawn_points.iter()
+        .map(|idx| ((*idx % MAPWIDTH) as i32, (*idx / MAPWIDTH) as i32));
+    if let Some((x, y)) = points.next() {
+        gold_pile(ecs, x, y, gold);
+    }
+    if let Some((x, y)) = points.next() {
+        random_potion(ecs, x, y);
+    }
+    let character = character::player_character(ecs);
+    for (x, y) in points {
+        character.random_card(ecs, x, y);
+    }
+}
 
-            if let Some(pos) = positions.get_mut(ent) {
-                pos.x = x;
-                pos.y = y;
+/// Places the act's boss in the center of the arena
+pub fn spawn_boss_room(ecs: &mut World, room: &Rect, map_depth: i32) {
+    let encounter: monsters::Encounters;
+    {
+        let boss_table = monsters::boss_table(map_depth);
+        let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+        encounter = boss_table.roll(&mut rng).unwrap();
+    }
+    let bosses = encounter.spawn(ecs);
+    let (x, y) = room.center();
+    let mut positions = ecs.write_storage::<Position>();
+    for (i, boss) in bosses.iter().enumerate() {
+        if let Some(pos) = positions.get_mut(*boss) {
+            pos.x = x + i as i32 * 2;
+            pos.y = y;
+        }
+    }
+}
+
+// Nothing is placed in deep water or on lava
+fn is_safe_spawn(map: &Map, idx: usize) -> bool {
+    map.tiles[idx].is_walkable() && map.tiles[idx] != TileType::Lava
+}
+
+// Finds up to count free tiles that are safe to spawn on, searching outwards from the given tile
+// through walkable tiles so that every tile found can be reached from it
+fn free_tiles_around(map: &Map, x: i32, y: i32, count: usize) -> Vec<(i32, i32)> {
+    let mut found: Vec<(i32, i32)> = Vec::new();
+    let mut visited = vec![false; map.tiles.len()];
+    let mut open: VecDeque<(i32, i32)> = VecDeque::new();
+    visited[map.xy_idx(x, y)] = true;
+    open.push_back((x, y));
+
+    while let Some((cx, cy)) = open.pop_front() {
+        let idx = map.xy_idx(cx, cy);
+        if !map.blocked[idx] && is_safe_spawn(map, idx) {
+            found.push((cx, cy));
+            if found.len() == count { break; }
+        }
+        for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (-1, -1), (1, -1), (-1, 1)].iter() {
+            let (nx, ny) = (cx + dx, cy + dy);
+            if nx < 0 || ny < 0 || nx >= map.width || ny >= map.height { continue; }
+            let next = map.xy_idx(nx, ny);
+            if !visited[next] && map.tiles[next].is_walkable() {
+                visited[next] = true;
+                open.push_back((nx, ny));
             }
         }
     }
+    found
+}
+
+// Spawns the monsters of an encounter at random points in a room, returning them
+fn spawn_encounter(ecs: &mut World, map: &Map, room: &Rect, encounter: monsters::Encounters) -> Vec<Entity> {
+    let entry = encounter.spawn(ecs);
+    let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+
+    // Decide spawn points
+    let mut spawn_points: Vec<usize> = Vec::new();
+    for _ in 0 .. entry.len() {
+        let mut added = false;
+        let mut tries = 0;
+        while !added && tries < 20 {
+            let x = (room.x1 + rng.roll_dice(1, i32::abs(room.x2 - room.x1))) as usize;
+            let y = (room.y1 + rng.roll_dice(1, i32::abs(room.y2 - room.y1))) as usize;
+            let idx = (y * MAPWIDTH) + x;
+            if !spawn_points.contains(&idx) && is_safe_spawn(map, idx) {
+                spawn_points.push(idx);
+                added = true;
+            } else {
+                tries += 1;
+            }
+        }
+    }
+
+    // Set spawn points
+    let mut positions = ecs.write_storage::<Position>();
+    for (i, idx) in spawn_points.iter().enumerate() {
+        let x = (*idx % MAPWIDTH) as i32;
+        let y = (*idx / MAPWIDTH) as i32;
+        let ent = entry[i];
+
+        if let Some(pos) = positions.get_mut(ent) {
+            pos.x = x;
+            pos.y = y;
+        }
+    }
+    entry
+}
 
+// Scatters potions and cards from the player's card pool around a room
+fn spawn_items(ecs: &mut World, map: &Map, room: &Rect) {
     let mut item_spawn_points: Vec<usize> = Vec::new();
     let mut card_spawn_points: Vec<usize> = Vec::new();
     {
@@ -152,13 +518,16 @@ pub fn spawn_room(ecs: &mut World, room: &Rect, map_depth: i32) {
         // Decide item spawn points
         for _ in 0 .. num_items {
             let mut added = false;
-            while !added {
+            let mut tries = 0;
+            while !added && tries < 20 {
                 let x = (room.x1 + rng.roll_dice(1, i32::abs(room.x2 - room.x1))) as usize;
                 let y = (room.y1 + rng.roll_dice(1, i32::abs(room.y2 - room.y1))) as usize;
                 let idx = (y * MAPWIDTH) + x;
-                if !item_spawn_points.contains(&idx) {
+                if !item_spawn_points.contains(&idx) && is_safe_spawn(map, idx) {
                     item_spawn_points.push(idx);
                     added = true;
+                } else {
+                    tries += 1;
                 }
             }
         }
@@ -166,13 +535,16 @@ pub fn spawn_room(ecs: &mut World, room: &Rect, map_depth: i32) {
         // Decide card spawn points
         for _ in 0 .. num_cards {
             let mut added = false;
-            while !added {
+            let mut tries = 0;
+            while !added && tries < 20 {
                 let x = (room.x1 + rng.roll_dice(1, i32::abs(room.x2 - room.x1))) as usize;
                 let y = (room.y1 + rng.roll_dice(1, i32::abs(room.y2 - room.y1))) as usize;
                 let idx = (y * MAPWIDTH) + x;
-                if !card_spawn_points.contains(&idx) {
+                if !card_spawn_points.contains(&idx) && is_safe_spawn(map, idx) {
                     card_spawn_points.push(idx);
                     added = true;
+                } else {
+                    tries += 1;
                 }
             }
         }
@@ -185,11 +557,11 @@ pub fn spawn_room(ecs: &mut World, room: &Rect, map_depth: i32) {
         random_potion(ecs, x as i32, y as i32)
     }
 
-    // Spawn cards
+    // Spawn cards from the player's card pool
+    let character = character::player_character(ecs);
     for idx in card_spawn_points.iter() {
         let x = *idx % MAPWIDTH;
         let y = *idx / MAPWIDTH;
-        cards::silent::random_card(ecs, x as i32, y as i32)
+        character.random_card(ecs, x as i32, y as i32)
     }
-
 }
\ No newline at end of file
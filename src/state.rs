@@ -1,10 +1,10 @@
 use specs::prelude::*;
-use rltk::{Rltk, GameState, Point};
+use rltk::{Rltk, GameState, Point, RandomNumberGenerator};
 
 use super::{
-    creature, deck, effects, gui, item,
-    map, menu, monsters, player, saveload, spawner, systems,
-    Position, Renderable, Gamelog, Map,
+    act, creature, deck, effects, gui, item,
+    gold, map, map_builders, menu, monsters, noise, player, saveload, spawner, systems,
+    character::Character, Position, Renderable, Gamelog, Gold, Map,
 };
 
 #[derive(PartialEq, Copy, Clone, Debug)]
@@ -16,11 +16,13 @@ pub enum RunState {
     MonsterTurn,
     ShowInventory,
     ShowHand { selection: i32 },
-    ShowTargeting { action:Entity, range: i32, radius: i32 },
+    ShowTargeting { action:Entity, range: i32 },
     DiscardCard { number: i32 },
     MainMenu { menu_selection: menu::MainMenuSelection },
+    CharacterSelect { selection: Character },
     SaveGame,
     NextLevel,
+    Victory,
 }
 
 pub struct State {
@@ -33,18 +35,59 @@ impl State {
         visibility_sys.run_now(&self.ecs);
         let mut inventory_sys = systems::InventorySystem{};
         inventory_sys.run_now(&self.ecs);
+        let mut noise_sys = systems::NoiseSystem{};
+        noise_sys.run_now(&self.ecs);
         let mut monster_sys = systems::MonsterSystem{};
         monster_sys.run_now(&self.ecs);
         systems::action::run(&mut self.ecs);
+        systems::trap::run(&mut self.ecs);
+        let mut terrain_sys = systems::TerrainSystem{};
+        terrain_sys.run_now(&self.ecs);
+        let mut orb_sys = systems::OrbSystem{};
+        orb_sys.run_now(&self.ecs);
         let mut damage_sys = systems::DamageSystem{};
         damage_sys.run_now(&self.ecs);
         let mut cleanup_sys = systems::DeadCleanupSystem{};
         cleanup_sys.run_now(&self.ecs);
+        spawner::spawn_queued_rewards(&mut self.ecs);
+        spawner::spawn_queued_splits(&mut self.ecs);
         let mut end_turn_sys = systems::EndTurnSystem{};
         end_turn_sys.run_now(&self.ecs);
         self.ecs.maintain();
         let mut map_sys = systems::MapIndexSystem{};
         map_sys.run_now(&self.ecs);
+        let mut combat_sys = systems::CombatSystem{};
+        combat_sys.run_now(&self.ecs);
+        self.refresh_intents();
+    }
+
+    // Replaces used monster intents with the next attack of their attack cycle
+    fn refresh_intents(&mut self) {
+        let mut to_update: Vec<(Entity, monsters::Attacks)> = Vec::new();
+        {
+            let entities = self.ecs.entities();
+            let attack_cycles = self.ecs.read_storage::<creature::AttackCycle>();
+            let intents = self.ecs.read_storage::<creature::Intent>();
+
+            for (ent, ac, intent) in (&entities, &attack_cycles, &intents).join() {
+                if intent.used {
+                    to_update.push((ent, ac.attacks[ac.cycle].clone()));
+                }
+            }
+        }
+
+        for (ent, attack) in to_update {
+            let new_intent = attack.to_attack(&mut self.ecs);
+            let old_intent = {
+                let mut intents = self.ecs.write_storage::<creature::Intent>();
+                let intent = intents.get_mut(ent).expect("Monster intent disappeared while refreshing");
+                let old_intent = intent.intent;
+                intent.intent = new_intent;
+                intent.used = false;
+                old_intent
+            };
+            self.ecs.delete_entity(old_intent).expect("Unable to delete old monster intent");
+        }
     }
 
     fn take_action(&mut self, runstate: RunState, result: (gui::ItemMenuResult, Option<Entity>)) -> RunState {
@@ -55,16 +98,11 @@ impl State {
             gui::ItemMenuResult::NoResponse => {},
             gui::ItemMenuResult::Selected => {
                 let action = result.1.unwrap();
-                let mut radius = 0;
-                if let Some(r) = self.ecs.read_storage::<item::AreaOfEffect>().get(action) {
-                    radius = r.radius;
-                }
 
                 if let Some(targeted_action) = self.ecs.read_storage::<item::Targeted>().get(action) {
                     newrunstate = RunState::ShowTargeting{
                         action,
-                        range: targeted_action.range, 
-                        radius
+                        range: targeted_action.range
                     };
                 } else {
                     let mut intent = self.ecs.write_storage::<creature::PerformAction>();
@@ -121,6 +159,45 @@ impl State {
         to_delete
     }
 
+    fn new_game(&mut self, character: Character) {
+        // Delete everything
+        self.ecs.delete_all();
+
+        // Create map, mark player spawn position
+        let mut map = self.build_floor(1);
+        let (player_x, player_y) = map.rooms[0].center();
+        {
+            let mut player_pos = self.ecs.write_resource::<Point>();
+            *player_pos = Point::new(player_x, player_y);
+        }
+
+        // Create player entity and update player <Entity> resource
+        let player_entity = spawner::player(&mut self.ecs, player_x, player_y, character);
+        {
+            let mut player_resource = self.ecs.write_resource::<Entity>();
+            *player_resource = player_entity;
+        }
+
+        // Spawn mobs
+        spawner::spawn_floor(&mut self.ecs, &mut map);
+
+        // Create starting deck and draw a hand
+        let mut deck = deck::Deck::default();
+        deck.gain_multiple_cards(character.starter_deck(&mut self.ecs));
+        for _ in 0 .. 5 {
+            deck.draw_card();
+        }
+        *self.ecs.write_resource::<deck::Deck>() = deck;
+
+        *self.ecs.write_resource::<Map>() = map;
+        *self.ecs.write_resource::<Gold>() = Gold{ amount: gold::STARTING_GOLD };
+        noise::reset(&mut self.ecs);
+
+        let mut log = self.ecs.fetch_mut::<Gamelog>();
+        log.entries.clear();
+        log.push(format!("You begin your climb as the {}.", character.to_name()));
+    }
+
     fn next_level(&mut self) {
         // Cleanup entities
         let to_delete = self.to_cleanup();
@@ -129,13 +206,13 @@ impl State {
         }
 
         // Build a new map
-        let map: Map;
-        let new_depth;
+        let mut map: Map;
+        let new_depth = self.ecs.fetch::<Map>().depth + 1;
+        let new_map = self.build_floor(new_depth);
         {
-            // Create map and update <Map> resource
+            // Update <Map> resource
             let mut map_resource = self.ecs.write_resource::<Map>();
-            new_depth = map_resource.depth + 1;
-            *map_resource = Map::new_map_rooms_and_corridors(new_depth);
+            *map_resource = new_map;
             map = map_resource.clone();
 
             // Update player position <Point> resource
@@ -158,13 +235,34 @@ impl State {
             }
         }
 
-        // Spawn mobs
-        for room in map.rooms.iter().skip(1) {
-            spawner::spawn_room(&mut self.ecs, room, new_depth);
-        }
+        // Spawn mobs and mark the elite room
+        spawner::spawn_floor(&mut self.ecs, &mut map);
+        *self.ecs.write_resource::<Map>() = map;
+        noise::reset(&mut self.ecs);
 
         let mut log = self.ecs.fetch_mut::<Gamelog>();
-        log.push("You descend to the next level.".to_string());
+        if act::floor_in_act(new_depth) == 1 {
+            log.push(format!("You enter {}.", act::Act::from_floor(new_depth).to_name()));
+        } else if act::is_boss_floor(new_depth) {
+            log.push("You descend into the boss's lair.".to_string());
+        } else {
+            log.push("You descend to the next level.".to_string());
+        }
+    }
+
+    // Picks a generator for the floor and builds its map
+    fn build_floor(&mut self, map_depth: i32) -> Map {
+        let mut rng = self.ecs.write_resource::<RandomNumberGenerator>();
+        let builder = map_builders::builder_for_floor(map_depth, &mut rng);
+        map_builders::build_floor(builder.as_ref(), map_depth, &mut rng)
+    }
+
+    // The run is won once the boss of the final floor is dead
+    fn final_boss_defeated(&self) -> bool {
+        let map = self.ecs.fetch::<Map>();
+        if !act::is_final_floor(map.depth) { return false; }
+        let bosses = self.ecs.read_storage::<creature::Boss>();
+        (&bosses).join().next().is_none()
     }
 }
 
@@ -180,7 +278,7 @@ impl GameState for State {
 
         // State machine
         match newrunstate {
-            RunState::MainMenu{..} => {}
+            RunState::MainMenu{..} | RunState::CharacterSelect{..} | RunState::Victory => {}
             _ => {
                 map::draw_map(&self.ecs, ctx);
                 {
@@ -195,6 +293,7 @@ impl GameState for State {
                         let idx = map.xy_idx(pos.x, pos.y);
                         if map.visible_tiles[idx] { ctx.set(pos.x, pos.y, render.fg, render.bg, render.glyph); }
                     }
+                    noise::draw_noise_field(&self.ecs, ctx);
         
                     gui::draw_ui(&self.ecs, ctx);
                 }
@@ -226,53 +325,6 @@ impl GameState for State {
             RunState::MonsterTurn => {
                 self.run_systems();
                 self.ecs.maintain();
-
-                // Find all used intents
-                let mut to_update: Vec<Option<monsters::Attacks>> = Vec::new();
-                {
-                    let attack_cycles = { self.ecs.read_storage::<creature::AttackCycle>().clone() };
-                    let intents = self.ecs.read_storage::<creature::Intent>();
-
-                    for (ac, intent) in (&attack_cycles, &intents).join() {
-                        match intent.used {
-                            true => to_update.push(Some(ac.attacks[ac.cycle].clone())),
-                            false => to_update.push(None),
-                        }
-                    }
-                }
-
-                // Generate new intents
-                let mut new_intents: Vec<Option<Entity>> = Vec::new();
-                {
-                    for attacks in to_update {
-                        match attacks {
-                            Some(atk) => new_intents.push(Some(atk.to_attack(&mut self.ecs))),
-                            None => new_intents.push(None)
-                        }
-                    }
-                }
-
-                // Assign new intents
-                let mut to_delete: Vec<Entity> = Vec::new();
-                {
-                    let mut monster_intents = self.ecs.write_storage::<creature::Intent>();
-
-                    for (i, mut intent) in (&mut monster_intents).join().enumerate() {
-                        match new_intents[i] {
-                            Some(new_intent) => {
-                                to_delete.push(intent.intent);
-                                intent.intent = new_intent;
-                            }
-                            None => {}
-                        }
-                    }
-                }
-
-                // Delete old intents
-                for old in to_delete {
-                    self.ecs.delete_entity(old).expect("Unable to delete old monster intent");
-                }
-
                 newrunstate = RunState::EndTurn{ player_end_turn: false };
             }
             RunState::ShowInventory => {
@@ -283,8 +335,8 @@ impl GameState for State {
                 let result = gui::pick_card(&mut self.ecs, selection);
                 newrunstate = self.take_action(newrunstate, result);
             }
-            RunState::ShowTargeting{action, range, radius} => {
-                let result = gui::ranged_target(&self.ecs, ctx, range, radius);
+            RunState::ShowTargeting{action, range} => {
+                let result = gui::ranged_target(&self.ecs, ctx, action, range);
                 match result.0 {
                     gui::ItemMenuResult::Cancel => newrunstate = RunState::AwaitingInput,
                     gui::ItemMenuResult::NoResponse => {},
@@ -332,24 +384,49 @@ impl GameState for State {
                     menu::MainMenuResult::NoSelection{ selected } => newrunstate = RunState::MainMenu{ menu_selection: selected },
                     menu::MainMenuResult::Selected{ selected } => {
                         match selected {
-                            menu::MainMenuSelection::NewGame => newrunstate = RunState::PreRun,
+                            menu::MainMenuSelection::NewGame => newrunstate = RunState::CharacterSelect{ selection: Character::Ironclad },
                             menu::MainMenuSelection::LoadGame => {
-                                saveload::load_game(&mut self.ecs);
-                                newrunstate = RunState::AwaitingInput;
+                                match saveload::load_game(&mut self.ecs) {
+                                    Ok(()) => newrunstate = RunState::PreRun,
+                                    Err(e) => {
+                                        eprintln!("Unable to load save: {}", e);
+                                        newrunstate = RunState::MainMenu{ menu_selection: menu::MainMenuSelection::NewGame };
+                                    }
+                                }
                             }
                             menu::MainMenuSelection::Quit => { ::std::process::exit(0); }
                         }
                     }
                 }
             }
+            RunState::CharacterSelect{selection} => {
+                let result = menu::character_select(ctx, selection);
+                match result {
+                    menu::CharacterSelectResult::NoSelection{ selected } => newrunstate = RunState::CharacterSelect{ selection: selected },
+                    menu::CharacterSelectResult::Selected{ selected } => {
+                        self.new_game(selected);
+                        newrunstate = RunState::PreRun;
+                    }
+                    menu::CharacterSelectResult::Cancel => newrunstate = RunState::MainMenu{ menu_selection: menu::MainMenuSelection::NewGame },
+                }
+            }
             RunState::SaveGame => {
-                saveload::save_game(&mut self.ecs);
+                saveload::save_game(&mut self.ecs).expect("Unable to save game");
                 newrunstate = RunState::MainMenu{ menu_selection : menu::MainMenuSelection::LoadGame };
             }
             RunState::NextLevel => {
                 self.next_level();
                 newrunstate = RunState::PreRun;
             }
+            RunState::Victory => {
+                if menu::victory(&self.ecs, ctx) {
+                    newrunstate = RunState::MainMenu{ menu_selection: menu::MainMenuSelection::NewGame };
+                }
+            }
+        }
+
+        if newrunstate == RunState::AwaitingInput && self.final_boss_defeated() {
+            newrunstate = RunState::Victory;
         }
 
         {
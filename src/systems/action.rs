@@ -1,13 +1,13 @@
 use specs::prelude::*;
 use super::super::{
-    Name, Position, creature, Gamelog,
-    item, deck, Map,
-    effects, status
+    Name, Position, creature, Gamelog, Gold,
+    item, cards, deck, noise, Map, map::{TileType, TileEffect},
+    effects, orb, passive, relic, stance, status
 };
 
 macro_rules! apply_status {
     ($status_storage:expr, $status_effect:ident,
-        $entity:expr, $log:expr, $names:expr, $targets:expr, $intent:expr) => {
+        $entity:expr, $log:expr, $names:expr, $targets:expr, $intent:expr $(, $field:ident: $value:expr)*) => {
         let mut affected_targets = Vec::new();
         if let Some(action) = $status_storage.get($intent.action) {
             for target in $targets.iter() {
@@ -23,7 +23,7 @@ macro_rules! apply_status {
             if let Some(already_affected) = $status_storage.get_mut(target.0) {
                 already_affected.turns += target.1;
             } else {
-                $status_storage.insert(target.0, status::$status_effect{ turns: target.1 }).expect("Unable to insert status");
+                $status_storage.insert(target.0, status::$status_effect{ turns: target.1 $(, $field: $value)* }).expect("Unable to insert status");
             }
         }
     }
@@ -45,6 +45,65 @@ macro_rules! apply_buff {
     }
 }
 
+macro_rules! apply_debuff {
+    ($debuff_type:ty, $debuff_stat:ident, $ecs:expr, $entity:expr, $log:expr, $names:expr, $combat_stats:expr, $targets:expr, $intent:expr) => {
+        let debuff = $ecs.read_storage::<$debuff_type>();
+        if let Some(action) = debuff.get($intent.action) {
+            for target in $targets.iter() {
+                if let Some(stats) = $combat_stats.get_mut(*target) {
+                    stats.$debuff_stat -= action.amount;
+                    $log.push(format!("{} uses {} and {} loses {} {}.",
+                        $names.get($entity).unwrap().name,
+                        $names.get($intent.action).unwrap().name,
+                        $names.get(*target).unwrap().name,
+                        action.amount,
+                        stringify!($debuff_stat)))
+                }
+            }
+        }
+    }
+}
+
+/// Moves a creature to another tile, keeping the map's blocked tiles and tile contents up to date
+pub fn relocate(ecs: &World, map: &mut Map, ent: Entity, dest: rltk::Point) {
+    let mut positions = ecs.write_storage::<Position>();
+    let blockers = ecs.read_storage::<creature::BlocksTile>();
+    if let Some(pos) = positions.get_mut(ent) {
+        let old_idx = map.xy_idx(pos.x, pos.y);
+        let new_idx = map.xy_idx(dest.x, dest.y);
+        map.tile_content[old_idx].retain(|e| *e != ent);
+        map.tile_content[new_idx].push(ent);
+        map.blocked[old_idx] = map.tile_content[old_idx].iter().any(|e| blockers.get(*e).is_some());
+        if blockers.get(ent).is_some() { map.blocked[new_idx] = true; }
+        pos.x = dest.x;
+        pos.y = dest.y;
+    }
+    if let Some(viewshed) = ecs.write_storage::<creature::Viewshed>().get_mut(ent) {
+        viewshed.dirty = true;
+    }
+    ecs.write_storage::<creature::EntityMoved>().insert(ent, creature::EntityMoved{})
+        .expect("Unable to insert creature::EntityMoved");
+    if ent == *ecs.fetch::<Entity>() {
+        let mut player_pos = ecs.write_resource::<rltk::Point>();
+        player_pos.x = dest.x;
+        player_pos.y = dest.y;
+    }
+}
+
+// Returns the creature standing on a tile, if any
+fn occupant(ecs: &World, map: &Map, point: rltk::Point) -> Option<Entity> {
+    let creatures = ecs.read_storage::<creature::Creature>();
+    let idx = map.xy_idx(point.x, point.y);
+    map.tile_content[idx].iter().find(|e| creatures.get(**e).is_some()).copied()
+}
+
+// Returns true if nothing stops a creature from being moved onto the tile
+fn is_open(ecs: &World, map: &Map, point: rltk::Point) -> bool {
+    if point.x < 1 || point.x > map.width - 2 || point.y < 1 || point.y > map.height - 2 { return false; }
+    let idx = map.xy_idx(point.x, point.y);
+    map.tiles[idx] != TileType::Wall && !map.blocked[idx] && occupant(ecs, map, point).is_none()
+}
+
 pub fn run(ecs: &mut World) {
     let mut gain_to_hand_queue: Vec<effects::GainableCard> = Vec::new();
     let mut gain_to_discard_queue: Vec<effects::GainableCard> = Vec::new();
@@ -53,7 +112,7 @@ pub fn run(ecs: &mut World) {
         let entities = ecs.entities();
         let player_entity = ecs.fetch::<Entity>();
         let mut log = ecs.fetch_mut::<Gamelog>();
-        let map = ecs.fetch::<Map>();
+        let mut map = ecs.fetch_mut::<Map>();
         let mut deck = ecs.fetch_mut::<deck::Deck>();
 
         let names = ecs.read_storage::<Name>();
@@ -65,26 +124,45 @@ pub fn run(ecs: &mut World) {
         let mut status_vulnerable = ecs.write_storage::<status::Vulnerable>();
         let mut status_frail = ecs.write_storage::<status::Frail>();
         let mut status_poison = ecs.write_storage::<status::Poison>();
+        let mut status_entangled = ecs.write_storage::<status::Entangled>();
+        let mut status_rooted = ecs.write_storage::<status::Rooted>();
+        let mut status_slowed = ecs.write_storage::<status::Slowed>();
+
+        let mut in_stance = ecs.write_storage::<stance::InStance>();
 
         for (entity, intent) in (&entities, &intent_action).join() {
             // Determine affected targets
             let mut targets: Vec<Entity> = Vec::new();
-            match intent.target {
+            // Projectiles land on the first creature in their way
+            let caster_pos = ecs.read_storage::<Position>().get(entity).map(|pos| rltk::Point::new(pos.x, pos.y));
+            let impact = intent.target.map(|target| match caster_pos {
+                Some(origin) if ecs.read_storage::<item::Projectile>().get(intent.action).is_some() =>
+                    map.line_of_fire(origin, target, true).last().copied().unwrap_or(target),
+                _ => target,
+            });
+
+            match impact {
                 None => { targets.push(entity); }
                 Some(target) => {
+                    let caster_pos = caster_pos.unwrap_or(target);
                     let creatures = ecs.read_storage::<creature::Creature>();
                     let aoe = ecs.read_storage::<item::AreaOfEffect>();
                     if let Some(area_effect) = aoe.get(intent.action) {
-                        let mut aoe_tiles = rltk::field_of_view(target, area_effect.radius, &*map);
-                        aoe_tiles.retain(|p| p.x > 0 && p.x < map.width-1 && p.y > 0 && p.y < map.height-1 );
-                        for tile_idx in aoe_tiles.iter() {
+                        for tile_idx in area_effect.tiles(&*map, caster_pos, target).iter() {
                             let idx = map.xy_idx(tile_idx.x, tile_idx.y);
                             for creature in map.tile_content[idx].iter() {
                                 if let Some(_) = creatures.get(*creature) {
-                                    if *creature != entity { targets.push(*creature); }
+                                    if *creature != entity || area_effect.friendly_fire { targets.push(*creature); }
                                 }
                             }
                         }
+
+                        // Without friendly fire, areas only hit the caster's enemies
+                        if !area_effect.friendly_fire && ecs.read_storage::<item::TargetsAllies>().get(intent.action).is_none() {
+                            let monsters = ecs.read_storage::<creature::Monster>();
+                            let caster_is_monster = monsters.get(entity).is_some();
+                            targets.retain(|t| monsters.get(*t).is_some() != caster_is_monster);
+                        }
                     } else {
                         let idx = map.xy_idx(target.x, target.y);
                         for creature in map.tile_content[idx].iter() {
@@ -94,23 +172,39 @@ pub fn run(ecs: &mut World) {
                         }
                     }
 
+                    // Support actions only affect the caster's allies, and a gremlin's only other gremlins
+                    if ecs.read_storage::<item::TargetsAllies>().get(intent.action).is_some() {
+                        let monsters = ecs.read_storage::<creature::Monster>();
+                        let gremlins = ecs.read_storage::<creature::Gremlin>();
+                        let caster_is_monster = monsters.get(entity).is_some();
+                        let caster_is_gremlin = gremlins.get(entity).is_some();
+                        targets.retain(|t| monsters.get(*t).is_some() == caster_is_monster
+                            && (!caster_is_gremlin || gremlins.get(*t).is_some()));
+                    }
+
                     // Move caster to targeted location
                     let effect_teleport = ecs.read_storage::<effects::Teleport>();
                     if let Some(_) = effect_teleport.get(intent.action) {
-                        let mut positions = ecs.write_storage::<Position>();
-                        let mut viewsheds = ecs.write_storage::<creature::Viewshed>();
-                        let mut ent_pos = positions.get_mut(entity).unwrap();
-                        let dest_idx = map.xy_idx(target.x, target.y);
+                        if status_rooted.get(entity).is_some() {
+                            log.push(format!("{} is Rooted and cannot teleport.", names.get(entity).unwrap().name));
+                        } else {
+                            let mut positions = ecs.write_storage::<Position>();
+                            let mut viewsheds = ecs.write_storage::<creature::Viewshed>();
+                            let mut ent_pos = positions.get_mut(entity).unwrap();
+                            let dest_idx = map.xy_idx(target.x, target.y);
 
-                        if !map.blocked[dest_idx] {
-                            ent_pos.x = target.x;
-                            ent_pos.y = target.y;
-                            if let Some(viewshed) = viewsheds.get_mut(entity) { viewshed.dirty = true; }
-                        }
-                        if entity == *player_entity {
-                            let mut player_pos = ecs.write_resource::<rltk::Point>();
-                            player_pos.x = ent_pos.x;
-                            player_pos.y = ent_pos.y;
+                            if !map.blocked[dest_idx] {
+                                ent_pos.x = target.x;
+                                ent_pos.y = target.y;
+                                if let Some(viewshed) = viewsheds.get_mut(entity) { viewshed.dirty = true; }
+                                ecs.write_storage::<creature::EntityMoved>().insert(entity, creature::EntityMoved{})
+                                    .expect("Unable to insert creature::EntityMoved");
+                            }
+                            if entity == *player_entity {
+                                let mut player_pos = ecs.write_resource::<rltk::Point>();
+                                player_pos.x = ent_pos.x;
+                                player_pos.y = ent_pos.y;
+                            }
                         }
                     }
                 }
@@ -142,6 +236,7 @@ pub fn run(ecs: &mut World) {
             // Deal damage to affected targets
             {
                 let effect_damage = ecs.read_storage::<effects::DealDamage>();
+                let awareness = ecs.read_storage::<creature::Awareness>();
                 let mut suffer_damage = ecs.write_storage::<creature::SufferDamage>();
                 if let Some(action) = effect_damage.get(intent.action) {
                     for target in targets.iter() {
@@ -153,11 +248,22 @@ pub fn run(ecs: &mut World) {
                             dmg = (dmg as f32 * 0.75) as i32;
                         }
 
+                        // Check for caster's stance
+                        if let Some(current) = in_stance.get(entity) {
+                            dmg = (dmg as f32 * current.stance.damage_dealt()) as i32;
+                        }
+
                         // Check for status::Vulnerable
                         if let Some(_) = status_vulnerable.get(*target) {
                             dmg = (dmg as f32 * 1.5) as i32;
                         }
 
+                        // Unaware monsters take extra damage from the attack that alerts them
+                        if awareness.get(*target).map_or(false, |a| a.state == creature::Alertness::Unaware) {
+                            dmg = (dmg as f32 * 1.5) as i32;
+                            log.push(format!("{} is caught unaware!", names.get(*target).unwrap().name));
+                        }
+
                         creature::SufferDamage::new_damage(&mut suffer_damage, *target, dmg);
                         log.push(format!("{} uses {} on {} for {} damage.",
                             names.get(entity).unwrap().name,
@@ -168,15 +274,344 @@ pub fn run(ecs: &mut World) {
                 }
             }
 
+            // Knock targets back, drag them in or trade places with them
+            {
+                let effect_push = ecs.read_storage::<effects::Push>();
+                let effect_pull = ecs.read_storage::<effects::Pull>();
+                let effect_swap = ecs.read_storage::<effects::Swap>();
+                let push = effect_push.get(intent.action);
+                let pull = effect_pull.get(intent.action);
+                let swap = effect_swap.get(intent.action);
+                if push.is_some() || pull.is_some() || swap.is_some() {
+                    let mut suffer_damage = ecs.write_storage::<creature::SufferDamage>();
+                    for target in targets.iter() {
+                        if *target == entity { continue; }
+                        if status_rooted.get(*target).is_some() {
+                            log.push(format!("{} is Rooted and cannot be moved.", names.get(*target).unwrap().name));
+                            continue;
+                        }
+                        let (caster_pos, target_pos) = {
+                            let positions = ecs.read_storage::<Position>();
+                            match (positions.get(entity), positions.get(*target)) {
+                                (Some(c), Some(t)) => (rltk::Point::new(c.x, c.y), rltk::Point::new(t.x, t.y)),
+                                _ => continue,
+                            }
+                        };
+
+                        if let Some(push) = push {
+                            let (dx, dy) = ((target_pos.x - caster_pos.x).signum(), (target_pos.y - caster_pos.y).signum());
+                            let mut dest = target_pos;
+                            for _ in 0 .. push.distance {
+                                let next = rltk::Point::new(dest.x + dx, dest.y + dy);
+                                if is_open(ecs, &*map, next) {
+                                    dest = next;
+                                    continue;
+                                }
+
+                                // Slamming into something hurts
+                                creature::SufferDamage::new_damage(&mut suffer_damage, *target, push.collision_damage);
+                                match occupant(ecs, &*map, next) {
+                                    Some(other) => log.push(format!("{} slams into {} for {} damage.",
+                                        names.get(*target).unwrap().name, names.get(other).unwrap().name, push.collision_damage)),
+                                    None => log.push(format!("{} slams into a wall for {} damage.",
+                                        names.get(*target).unwrap().name, push.collision_damage)),
+                                }
+                                break;
+                            }
+                            if dest != target_pos {
+                                relocate(ecs, &mut *map, *target, dest);
+                            }
+                        }
+
+                        if let Some(pull) = pull {
+                            let (dx, dy) = ((caster_pos.x - target_pos.x).signum(), (caster_pos.y - target_pos.y).signum());
+                            let mut dest = target_pos;
+                            for _ in 0 .. pull.distance {
+                                let next = rltk::Point::new(dest.x + dx, dest.y + dy);
+                                if !is_open(ecs, &*map, next) { break; }
+                                dest = next;
+                            }
+                            if dest != target_pos {
+                                relocate(ecs, &mut *map, *target, dest);
+                                log.push(format!("{} is pulled in.", names.get(*target).unwrap().name));
+                            }
+                        }
+
+                        if swap.is_some() {
+                            if status_rooted.get(entity).is_some() {
+                                log.push(format!("{} is Rooted and cannot swap places.", names.get(entity).unwrap().name));
+                            } else {
+                                relocate(ecs, &mut *map, *target, caster_pos);
+                                relocate(ecs, &mut *map, entity, target_pos);
+                                log.push(format!("{} swaps places with {}.",
+                                    names.get(entity).unwrap().name, names.get(*target).unwrap().name));
+                            }
+                            // Only one target can take the caster's place
+                            break;
+                        }
+                    }
+                }
+            }
+
+            // Lay terrain over the affected tiles
+            {
+                let effect_terrain = ecs.read_storage::<effects::CreateTerrain>();
+                if let Some(action) = effect_terrain.get(intent.action) {
+                    if let Some(center) = impact.or(caster_pos) {
+                        let tiles = match ecs.read_storage::<item::AreaOfEffect>().get(intent.action) {
+                            Some(area_effect) => area_effect.tiles(&*map, caster_pos.unwrap_or(center), center),
+                            None => vec![center],
+                        };
+                        for tile in tiles {
+                            let idx = map.xy_idx(tile.x, tile.y);
+                            if map.tiles[idx].is_solid() { continue; }
+                            map.tile_effects.insert(idx, TileEffect{ effect: action.terrain, turns: action.turns });
+                        }
+                        log.push(format!("{} uses {} and the area is covered in {}.",
+                            names.get(entity).unwrap().name,
+                            names.get(intent.action).unwrap().name,
+                            action.terrain.to_name()));
+
+                        // Smoke changes what everyone can see
+                        for viewshed in (&mut ecs.write_storage::<creature::Viewshed>()).join() {
+                            viewshed.dirty = true;
+                        }
+                    }
+                }
+            }
+
+            // Reveal traps around the caster
+            {
+                let effect_reveal = ecs.read_storage::<effects::RevealTraps>();
+                if let (Some(action), Some(origin)) = (effect_reveal.get(intent.action), caster_pos) {
+                    let found = map.reveal_traps(origin, action.radius);
+                    log.push(format!("{} uses {} and spots {} trap(s).",
+                        names.get(entity).unwrap().name,
+                        names.get(intent.action).unwrap().name,
+                        found));
+                }
+            }
+
+            // Give block and strength to affected targets
+            {
+                let effect_grant_block = ecs.read_storage::<effects::GrantBlock>();
+                let effect_grant_strength = ecs.read_storage::<effects::GrantStrength>();
+                for target in targets.iter() {
+                    if let Some(stats) = combat_stats.get_mut(*target) {
+                        if let Some(action) = effect_grant_block.get(intent.action) {
+                            stats.block += action.amount;
+                            log.push(format!("{} uses {} and {} gains {} block.",
+                                names.get(entity).unwrap().name,
+                                names.get(intent.action).unwrap().name,
+                                names.get(*target).unwrap().name,
+                                action.amount))
+                        }
+                        if let Some(action) = effect_grant_strength.get(intent.action) {
+                            stats.strength += action.amount;
+                            log.push(format!("{} uses {} and {} gains {} strength.",
+                                names.get(entity).unwrap().name,
+                                names.get(intent.action).unwrap().name,
+                                names.get(*target).unwrap().name,
+                                action.amount))
+                        }
+                    }
+                }
+            }
+
+            // Change caster's stance
+            {
+                let effect_stance = ecs.read_storage::<effects::ChangeStance>();
+                let relics = ecs.read_storage::<relic::Relics>();
+                let mut player = ecs.write_storage::<creature::Player>();
+                if let Some(action) = effect_stance.get(intent.action) {
+                    if let Some(current) = in_stance.get_mut(entity) {
+                        if current.stance != action.stance {
+                            let old_stance = current.stance;
+                            current.stance = action.stance;
+                            match action.stance {
+                                stance::Stance::Neutral => log.push(format!("{} exits {}.",
+                                    names.get(entity).unwrap().name,
+                                    old_stance.to_name())),
+                                new_stance => log.push(format!("{} enters {}.",
+                                    names.get(entity).unwrap().name,
+                                    new_stance.to_name())),
+                            }
+
+                            let mut energy = old_stance.exit_energy() + action.stance.enter_energy();
+                            if old_stance == stance::Stance::Calm {
+                                if let Some(r) = relics.get(entity) {
+                                    if r.relics.contains(&relic::Relic::VioletLotus) { energy += 1; }
+                                }
+                            }
+                            if let Some(player_energy) = player.get_mut(entity) {
+                                if energy > 0 {
+                                    player_energy.energy += energy;
+                                    log.push(format!("{} gains {} energy.",
+                                        names.get(entity).unwrap().name,
+                                        energy))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             // Apply stat buffs to caster
             apply_buff!(effects::BuffStrength, strength, ecs, entity, log, names, combat_stats, intent);
             apply_buff!(effects::BuffDexterity, dexterity, ecs, entity, log, names, combat_stats, intent);
 
+            // Grant passive to caster
+            {
+                let effect_passive = ecs.read_storage::<effects::GainPassive>();
+                let mut passives = ecs.write_storage::<passive::Passives>();
+                if let Some(action) = effect_passive.get(intent.action) {
+                    if let Some(current) = passives.get_mut(entity) {
+                        current.add(action.passive);
+                    } else {
+                        passives.insert(entity, passive::Passives::new(vec![action.passive]))
+                            .expect("Unable to insert passive::Passives");
+                    }
+                    log.push(format!("{} uses {} and gains {}.",
+                        names.get(entity).unwrap().name,
+                        names.get(intent.action).unwrap().name,
+                        action.passive.to_name()))
+                }
+            }
+
+            // Apply stat debuffs to affected targets
+            apply_debuff!(effects::DebuffStrength, strength, ecs, entity, log, names, combat_stats, targets, intent);
+            apply_debuff!(effects::DebuffDexterity, dexterity, ecs, entity, log, names, combat_stats, targets, intent);
+
+            // Apply hp loss to caster
+            {
+                let effect_lose_hp = ecs.read_storage::<effects::LoseHp>();
+                if let Some(action) = effect_lose_hp.get(intent.action) {
+                    if let Some(stats) = combat_stats.get_mut(entity) {
+                        stats.hp -= action.amount;
+                        log.push(format!("{} uses {} and loses {} HP.",
+                            names.get(entity).unwrap().name,
+                            names.get(intent.action).unwrap().name,
+                            action.amount))
+                    }
+                }
+            }
+
+            // Apply energy gain to caster
+            {
+                let effect_energy = ecs.read_storage::<effects::GainEnergy>();
+                let mut player = ecs.write_storage::<creature::Player>();
+                if let Some(action) = effect_energy.get(intent.action) {
+                    if let Some(player_energy) = player.get_mut(entity) {
+                        player_energy.energy += action.amount;
+                        log.push(format!("{} uses {} and gains {} energy.",
+                            names.get(entity).unwrap().name,
+                            names.get(intent.action).unwrap().name,
+                            action.amount))
+                    }
+                }
+            }
+
+            // Grant stealth to caster
+            {
+                let effect_stealth = ecs.read_storage::<effects::GainStealth>();
+                let mut stealth = ecs.write_storage::<creature::Stealth>();
+                if let Some(action) = effect_stealth.get(intent.action) {
+                    if let Some(caster_stealth) = stealth.get_mut(entity) {
+                        caster_stealth.bonus += action.amount;
+                        log.push(format!("{} uses {} and gains {} stealth.",
+                            names.get(entity).unwrap().name,
+                            names.get(intent.action).unwrap().name,
+                            action.amount))
+                    }
+                }
+            }
+
+            // Queue healing for the end of combat on caster
+            {
+                let mut effect_heal = ecs.write_storage::<effects::HealAfterCombat>();
+                let queued_heal = effect_heal.get(intent.action).map(|action| action.amount);
+                if let Some(amount) = queued_heal {
+                    if let Some(already_queued) = effect_heal.get_mut(entity) {
+                        already_queued.amount += amount;
+                    } else {
+                        effect_heal.insert(entity, effects::HealAfterCombat{ amount }).expect("Unable to insert effects::HealAfterCombat");
+                    }
+                    log.push(format!("{} will heal {} HP at the end of combat.",
+                        names.get(entity).unwrap().name,
+                        amount))
+                }
+            }
+
+            // Steal gold from the player
+            {
+                let effect_steal = ecs.read_storage::<effects::StealGold>();
+                let mut thieves = ecs.write_storage::<creature::Thief>();
+                if let Some(action) = effect_steal.get(intent.action) {
+                    if targets.contains(&*player_entity) {
+                        let stolen = ecs.fetch_mut::<Gold>().steal(action.amount);
+                        if stolen > 0 {
+                            if let Some(thief) = thieves.get_mut(entity) {
+                                thief.stolen += stolen;
+                            } else {
+                                thieves.insert(entity, creature::Thief{ stolen }).expect("Unable to insert creature::Thief");
+                            }
+                            log.push(format!("{} steals {} gold!",
+                                names.get(entity).unwrap().name,
+                                stolen))
+                        }
+                    }
+                }
+            }
+
+            // Caster starts fleeing from the player
+            {
+                let effect_flee = ecs.read_storage::<effects::Flee>();
+                let mut fleeing = ecs.write_storage::<creature::Fleeing>();
+                if effect_flee.get(intent.action).is_some() && fleeing.get(entity).is_none() {
+                    fleeing.insert(entity, creature::Fleeing{ unseen_turns: 0, escapes: true }).expect("Unable to insert creature::Fleeing");
+                    log.push(format!("{} uses {} and flees!",
+                        names.get(entity).unwrap().name,
+                        names.get(intent.action).unwrap().name))
+                }
+            }
+
+            // Channel and evoke caster's orbs
+            {
+                let effect_slots = ecs.read_storage::<effects::GainOrbSlots>();
+                let effect_channel = ecs.read_storage::<effects::ChannelOrb>();
+                let effect_evoke = ecs.read_storage::<effects::EvokeOrb>();
+                let mut orb_slots = ecs.write_storage::<orb::OrbSlots>();
+                if let Some(slots) = orb_slots.get_mut(entity) {
+                    if let Some(action) = effect_slots.get(intent.action) {
+                        slots.add_slots(action.number as usize);
+                        log.push(format!("{} uses {} and now has {} orb slots.",
+                            names.get(entity).unwrap().name,
+                            names.get(intent.action).unwrap().name,
+                            slots.max_slots))
+                    }
+                    if let Some(action) = effect_channel.get(intent.action) {
+                        for _ in 0 .. action.number {
+                            slots.channel(action.orb);
+                        }
+                        log.push(format!("{} channels {} {}.",
+                            names.get(entity).unwrap().name,
+                            action.number,
+                            action.orb.to_name()))
+                    }
+                    if let Some(action) = effect_evoke.get(intent.action) {
+                        slots.evoke(action.number);
+                    }
+                }
+            }
+
             // Apply status effects to affected targets
             apply_status!(status_weak, Weak, entity, log, names, targets, intent);
             apply_status!(status_vulnerable, Vulnerable, entity, log, names, targets, intent);
             apply_status!(status_frail, Frail, entity, log, names, targets, intent);
             apply_status!(status_poison, Poison, entity, log, names, targets, intent);
+            apply_status!(status_entangled, Entangled, entity, log, names, targets, intent);
+            apply_status!(status_rooted, Rooted, entity, log, names, targets, intent);
+            apply_status!(status_slowed, Slowed, entity, log, names, targets, intent, started: false);
 
             // Draw cards
             {
@@ -204,6 +639,30 @@ pub fn run(ecs: &mut World) {
                 }   
             }
 
+            // Enraged monsters that can see the player react to Skills
+            if entity == *player_entity && ecs.read_storage::<item::Card>().get(intent.action).is_some()
+                && cards::card_type(ecs, intent.action) == cards::CardType::Skill {
+                let enrage = ecs.read_storage::<creature::Enrage>();
+                let viewsheds = ecs.read_storage::<creature::Viewshed>();
+                let player_pos = ecs.fetch::<rltk::Point>();
+                for (monster, enraged, viewshed) in (&entities, &enrage, &viewsheds).join() {
+                    if !viewshed.visible_tiles.contains(&*player_pos) { continue; }
+                    if let Some(stats) = combat_stats.get_mut(monster) {
+                        stats.strength += enraged.amount;
+                        log.push(format!("{} is enraged and gains {} strength.",
+                            names.get(monster).unwrap().name,
+                            enraged.amount))
+                    }
+                }
+            }
+
+            // Attacks played by the player make noise
+            if entity == *player_entity && ecs.read_storage::<item::Card>().get(intent.action).is_some()
+                && cards::card_type(ecs, intent.action) == cards::CardType::Attack {
+                let player_pos = ecs.fetch::<rltk::Point>();
+                ecs.fetch_mut::<noise::NoiseEvents>().emit(player_pos.x, player_pos.y, noise::ATTACK_NOISE);
+            }
+
             // Discard used card or remove used potion
             {
                 let mut player = ecs.write_storage::<creature::Player>();
@@ -227,6 +686,10 @@ pub fn run(ecs: &mut World) {
                         deck.discard_card(intent.action, false);
                     }
                 } else if let Some(_) = potions.get(intent.action) {
+                    // Thrown potions shatter loudly where they land
+                    if let Some(target) = intent.target {
+                        ecs.fetch_mut::<noise::NoiseEvents>().emit(target.x, target.y, noise::SHATTER_NOISE);
+                    }
                     entities.delete(intent.action).expect("Failed to delete entity");
                 }
             }
@@ -0,0 +1,83 @@
+use specs::prelude::*;
+use super::super::{
+    Position, Gamelog,
+    creature, effects, orb, relic, stance,
+    deck::Deck,
+};
+
+use rltk::Point;
+
+pub struct CombatSystem {}
+
+impl<'a> System<'a> for CombatSystem {
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, Gamelog>,
+        WriteExpect<'a, Deck>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, creature::Monster>,
+        ReadStorage<'a, creature::Viewshed>,
+        ReadStorage<'a, relic::Relics>,
+        WriteStorage<'a, creature::InCombat>,
+        WriteStorage<'a, creature::CombatStats>,
+        WriteStorage<'a, effects::HealAfterCombat>,
+        WriteStorage<'a, orb::OrbSlots>,
+        WriteStorage<'a, stance::InStance>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (player_entity, mut log, mut deck, positions, monsters, viewsheds, relics,
+            mut in_combat, mut combat_stats, mut heal_after_combat, mut orb_slots, mut in_stance) = data;
+
+        // Combat lasts as long as a monster is in view of the player
+        let monster_in_view = match viewsheds.get(*player_entity) {
+            Some(viewshed) => {
+                (&positions, &monsters).join()
+                    .any(|(pos, _)| viewshed.visible_tiles.contains(&Point::new(pos.x, pos.y)))
+            }
+            None => false
+        };
+        let was_in_combat = in_combat.get(*player_entity).is_some();
+
+        if monster_in_view && !was_in_combat {
+            // Start of combat
+            in_combat.insert(*player_entity, creature::InCombat{}).expect("Unable to insert creature::InCombat");
+            if let Some(player_relics) = relics.get(*player_entity) {
+                if player_relics.relics.contains(&relic::Relic::RingOfTheSnake) {
+                    for _ in 0 .. 2 { deck.draw_card(); }
+                    log.push("Ring of the Snake draws 2 additional cards.".to_string());
+                }
+                if player_relics.relics.contains(&relic::Relic::CrackedCore) {
+                    if let Some(slots) = orb_slots.get_mut(*player_entity) {
+                        slots.channel(orb::Orb::Lightning);
+                        log.push("Cracked Core channels 1 Lightning.".to_string());
+                    }
+                }
+            }
+        } else if !monster_in_view && was_in_combat {
+            // End of combat
+            in_combat.remove(*player_entity);
+            let mut heal = 0;
+            if let Some(player_relics) = relics.get(*player_entity) {
+                if player_relics.relics.contains(&relic::Relic::BurningBlood) { heal += 6; }
+            }
+            if let Some(queued) = heal_after_combat.remove(*player_entity) {
+                heal += queued.amount;
+            }
+            if let Some(current) = in_stance.get_mut(*player_entity) {
+                if current.stance != stance::Stance::Neutral {
+                    current.stance = stance::Stance::Neutral;
+                    log.push("You return to Neutral stance.".to_string());
+                }
+            }
+
+            if let Some(stats) = combat_stats.get_mut(*player_entity) {
+                if heal > 0 {
+                    let healed = i32::min(heal, stats.max_hp - stats.hp);
+                    stats.hp += healed;
+                    log.push(format!("Combat is over. You heal {} HP.", healed));
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
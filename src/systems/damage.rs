@@ -1,21 +1,53 @@
 use specs::prelude::*;
-use super::super::{Gamelog, creature, Name};
+use super::super::{Gamelog, Map, Position, act, creature, map::TileType, monsters, noise, passive, spawner::RewardQueue, stance, status, Name};
 
 pub struct DamageSystem {}
 pub struct DeadCleanupSystem {}
 
 impl<'a> System<'a> for DamageSystem {
     type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, rltk::Point>,
+        WriteExpect<'a, Gamelog>,
+        WriteExpect<'a, noise::NoiseEvents>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Position>,
         WriteStorage<'a, creature::CombatStats>,
         WriteStorage<'a, creature::SufferDamage>,
+        ReadStorage<'a, stance::InStance>,
+        WriteStorage<'a, creature::Asleep>,
+        WriteStorage<'a, creature::Awareness>,
+        WriteStorage<'a, creature::Behaviour>,
+        WriteStorage<'a, creature::Splits>,
+        WriteStorage<'a, creature::ModeShift>,
+        WriteStorage<'a, creature::AttackCycle>,
+        WriteStorage<'a, creature::Intent>,
+        WriteStorage<'a, passive::Passives>,
     );
 
     fn run(&mut self, data : Self::SystemData) {
-        let (mut stats, mut damage) = data;
+        let (entities, player_pos, mut log, mut noises, names, positions, mut stats, mut damage, in_stance, mut asleep, mut awareness,
+            mut behaviours, mut splits, mut mode_shifts, mut attack_cycles, mut intents, mut passives) = data;
+
+        for (ent, mut stats, damage, current, sleep) in (&entities, &mut stats, &damage, in_stance.maybe(), (&mut asleep).maybe()).join() {
+            // Being hit wakes sleeping monsters, alerts unaware ones and provokes guards
+            if let Some(sleep) = sleep {
+                sleep.turns = 0;
+            }
+            if let Some(aware) = awareness.get_mut(ent) {
+                aware.notice(*player_pos);
+            }
+            if let Some(creature::Behaviour::Guard{ provoked }) = behaviours.get_mut(ent) {
+                *provoked = true;
+            }
 
-        for (mut stats, damage) in (&mut stats, &damage).join() {
             let mut total_damage = damage.amount.iter().sum::<i32>();
 
+            // Check for stances that increase damage taken
+            if let Some(current) = current {
+                total_damage = (total_damage as f32 * current.stance.damage_taken()) as i32;
+            }
+
             // Try to damage block
             if stats.block > 0 {
                 let block_damage = i32::min(stats.block, total_damage);
@@ -25,6 +57,81 @@ impl<'a> System<'a> for DamageSystem {
 
             // Try to damage hp
             stats.hp -= total_damage;
+
+            // Taking damage makes noise
+            if total_damage > 0 {
+                if let Some(pos) = positions.get(ent) {
+                    noises.emit(pos.x, pos.y, noise::DAMAGE_NOISE);
+                }
+            }
+            if stats.hp < 1 { continue; }
+
+            // Trigger passives that take effect when damaged
+            if let Some(creature_passives) = passives.get_mut(ent) {
+                if total_damage > 0 {
+                    for p in creature_passives.triggered(passive::Trigger::Damaged) {
+                        match p {
+                            passive::Passive::CurlUp{amount} => {
+                                stats.block += amount;
+                                if let Some(ent_name) = names.get(ent) {
+                                    log.push(format!("{} curls up and gains {} block.", ent_name.name, amount));
+                                }
+                            }
+                            passive::Passive::Angry{amount} => {
+                                stats.strength += amount;
+                                if let Some(ent_name) = names.get(ent) {
+                                    log.push(format!("{} gets angry and gains {} strength.", ent_name.name, amount));
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    creature_passives.expire(passive::Trigger::Damaged);
+                }
+            }
+
+            // Splitting monsters telegraph the split once they fall to half health
+            if let Some(split) = splits.get_mut(ent) {
+                if !split.ready && stats.hp <= stats.max_hp / 2 {
+                    split.ready = true;
+                    if let (Some(ac), Some(intent)) = (attack_cycles.get_mut(ent), intents.get_mut(ent)) {
+                        ac.switch_sequential(vec![monsters::Attacks::Pass{ name: "Split".to_string(), range: 8 }]);
+                        intent.used = true;
+                    }
+                    if let Some(ent_name) = names.get(ent) {
+                        log.push(format!("{} is about to split!", ent_name.name));
+                    }
+                }
+            }
+
+            // Attack cycles with a low health phase switch over once health falls far enough
+            if let (Some(ac), Some(intent)) = (attack_cycles.get_mut(ent), intents.get_mut(ent)) {
+                if ac.enter_phase(stats.hp, stats.max_hp) {
+                    intent.used = true;
+                    if let Some(ent_name) = names.get(ent) {
+                        log.push(format!("{} changes tactics!", ent_name.name));
+                    }
+                }
+            }
+
+            // Mode shifting monsters turn defensive after losing enough health
+            if let Some(mode) = mode_shifts.get_mut(ent) {
+                if !mode.defending {
+                    mode.damage_taken += total_damage;
+                    if mode.damage_taken >= mode.threshold {
+                        mode.defending = true;
+                        mode.damage_taken = 0;
+                        stats.block += mode.block;
+                        if let (Some(ac), Some(intent)) = (attack_cycles.get_mut(ent), intents.get_mut(ent)) {
+                            ac.switch_sequential(mode.defensive.clone());
+                            intent.used = true;
+                        }
+                        if let Some(ent_name) = names.get(ent) {
+                            log.push(format!("{} shifts into Defensive Mode and gains {} block.", ent_name.name, mode.block));
+                        }
+                    }
+                }
+            }
         }
 
         damage.clear();
@@ -36,14 +143,27 @@ impl<'a>System<'a> for DeadCleanupSystem {
         Entities<'a>,
         ReadExpect<'a, Entity>,
         WriteExpect<'a, Gamelog>,
+        WriteExpect<'a, Map>,
+        WriteExpect<'a, RewardQueue>,
         ReadStorage<'a, Name>,
+        ReadStorage<'a, Position>,
         ReadStorage<'a, creature::CombatStats>,
+        ReadStorage<'a, creature::Boss>,
+        ReadStorage<'a, creature::Elite>,
+        ReadStorage<'a, creature::EliteGroup>,
+        ReadStorage<'a, creature::Thief>,
+        ReadStorage<'a, creature::Creature>,
+        ReadStorage<'a, passive::Passives>,
+        WriteStorage<'a, status::Vulnerable>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, player_entity, mut log, names, combat_stats) = data;
+        let (entities, player_entity, mut log, mut map, mut rewards, names, positions, combat_stats,
+            bosses, elites, elite_groups, thieves, creatures, passives, mut status_vulnerable) = data;
 
         let mut dead: Vec<Entity> = Vec::new();
+        let mut rewarded_groups: Vec<usize> = Vec::new();
+        let mut fallen_boss: Option<(i32, i32)> = None;
         for (entity, stats) in (&entities, &combat_stats).join() {
             if stats.hp < 1 {
                 if entity == *player_entity {
@@ -53,10 +173,74 @@ impl<'a>System<'a> for DeadCleanupSystem {
                         log.push(format!("{} is dead!", victim_name.name));
                     }
                     dead.push(entity);
+
+                    // Elites leave their rewards behind, once per group of elites
+                    if elites.get(entity).is_some() {
+                        let mut drops_reward = true;
+                        if let Some(group) = elite_groups.get(entity) {
+                            let others_alive = (&entities, &elite_groups, &combat_stats).join()
+                                .any(|(other, other_group, other_stats)| other != entity && other_group.room == group.room && other_stats.hp >= 1);
+                            drops_reward = !others_alive && !rewarded_groups.contains(&group.room);
+                            if drops_reward { rewarded_groups.push(group.room); }
+                        }
+                        if drops_reward {
+                            if let Some(pos) = positions.get(entity) {
+                                rewards.positions.push((pos.x, pos.y));
+                            }
+                        }
+                    }
+
+                    // Thieves drop the gold they stole
+                    if let (Some(thief), Some(pos)) = (thieves.get(entity), positions.get(entity)) {
+                        if thief.stolen > 0 {
+                            rewards.gold.push((pos.x, pos.y, thief.stolen));
+                        }
+                    }
+
+                    if bosses.get(entity).is_some() {
+                        if let Some(pos) = positions.get(entity) {
+                            fallen_boss = Some((pos.x, pos.y));
+                        }
+                    }
+
+                    // Trigger passives that take effect on death
+                    if let (Some(creature_passives), Some(pos)) = (passives.get(entity), positions.get(entity)) {
+                        for p in creature_passives.triggered(passive::Trigger::Death) {
+                            if let passive::Passive::SporeCloud{turns, radius} = p {
+                                if let Some(victim_name) = names.get(entity) {
+                                    log.push(format!("{} releases a Spore Cloud!", victim_name.name));
+                                }
+                                let origin = rltk::Point::new(pos.x, pos.y);
+                                for (nearby, _, nearby_pos, nearby_stats) in (&entities, &creatures, &positions, &combat_stats).join() {
+                                    let distance = rltk::DistanceAlg::Pythagoras.distance2d(origin, rltk::Point::new(nearby_pos.x, nearby_pos.y));
+                                    if nearby == entity || nearby_stats.hp < 1 || distance > radius as f32 { continue; }
+
+                                    if let Some(vulnerable) = status_vulnerable.get_mut(nearby) {
+                                        vulnerable.turns += turns;
+                                    } else {
+                                        status_vulnerable.insert(nearby, status::Vulnerable{ turns }).expect("Unable to insert status");
+                                    }
+                                    if let Some(nearby_name) = names.get(nearby) {
+                                        log.push(format!("{} is Vulnerable for {} turns.", nearby_name.name, turns));
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
             }
         }
 
+        // The last boss to fall opens the way to the next act
+        if let Some((x, y)) = fallen_boss {
+            let bosses_remain = (&bosses, &combat_stats).join().any(|(_, stats)| stats.hp >= 1);
+            if !bosses_remain && !act::is_final_floor(map.depth) {
+                let idx = map.xy_idx(x, y);
+                map.tiles[idx] = TileType::DownStairs;
+                log.push("A staircase opens where the boss fell.".to_string());
+            }
+        }
+
         for victim in dead {
             entities.delete(victim).expect("Unable to delete");
         }
@@ -1,5 +1,5 @@
 use specs::prelude::*;
-use super::super::{Name, Gamelog, RunState, creature, status};
+use super::super::{Name, Gamelog, RunState, creature, stance, status};
 
 pub struct EndTurnSystem {}
 
@@ -37,14 +37,20 @@ impl<'a> System<'a> for EndTurnSystem {
         ReadStorage<'a, Name>,
         ReadStorage<'a, creature::Monster>,
         WriteStorage<'a, creature::CombatStats>,
+        WriteStorage<'a, creature::Stealth>,
         WriteStorage<'a, status::Weak>,
         WriteStorage<'a, status::Vulnerable>,
         WriteStorage<'a, status::Frail>,
+        WriteStorage<'a, status::Entangled>,
+        WriteStorage<'a, status::Rooted>,
+        WriteStorage<'a, status::Slowed>,
+        WriteStorage<'a, stance::InStance>,
     );
 
     fn run(&mut self, data : Self::SystemData) {
-        let (entities, player_entity, runstate, mut log, names, monsters, mut combat_stats,
-            mut status_weak, mut status_vulnerable, mut status_frail) = data;
+        let (entities, player_entity, runstate, mut log, names, monsters, mut combat_stats, mut stealth,
+            mut status_weak, mut status_vulnerable, mut status_frail,
+            mut status_entangled, mut status_rooted, mut status_slowed, mut in_stance) = data;
 
         // Skip if not on endturn
         let player_turn: bool;
@@ -57,6 +63,26 @@ impl<'a> System<'a> for EndTurnSystem {
         decay_status!(status_weak, "Weak", log, player_entity, player_turn, entities, names, monsters);
         decay_status!(status_vulnerable, "Vulnerable", log, player_entity, player_turn, entities, names, monsters);
         decay_status!(status_frail, "Frail", log, player_entity, player_turn, entities, names, monsters);
+        decay_status!(status_entangled, "Entangled", log, player_entity, player_turn, entities, names, monsters);
+        decay_status!(status_rooted, "Rooted", log, player_entity, player_turn, entities, names, monsters);
+        decay_status!(status_slowed, "Slowed", log, player_entity, player_turn, entities, names, monsters);
+
+        // Stealth from cards wears off as the player's turn ends
+        if player_turn {
+            if let Some(player_stealth) = stealth.get_mut(*player_entity) {
+                player_stealth.bonus = i32::max(0, player_stealth.bonus - 1);
+            }
+        }
+
+        // Divinity is exited at the start of the player's turn
+        if !player_turn {
+            if let Some(current) = in_stance.get_mut(*player_entity) {
+                if current.stance == stance::Stance::Divinity {
+                    current.stance = stance::Stance::Neutral;
+                    log.push("You exit Divinity.".to_string());
+                }
+            }
+        }
 
         // Decay stats
         for (ent, mut stats) in (&entities, &mut combat_stats).join() {
@@ -1,6 +1,6 @@
 use specs::prelude::*;
 use super::super::{
-    Position, Name, Gamelog,
+    Position, Name, Gamelog, Gold, Map,
     creature, item, deck::Deck,
 };
 
@@ -8,22 +8,40 @@ pub struct InventorySystem {}
 
 impl<'a> System<'a> for InventorySystem {
     type SystemData = (
+        Entities<'a>,
         WriteExpect<'a, Deck>,
         WriteExpect<'a, Gamelog>,
+        WriteExpect<'a, Gold>,
+        WriteExpect<'a, Map>,
         ReadStorage<'a, Name>,
         WriteStorage<'a, Position>,
         WriteStorage<'a, creature::PickupItem>,
         ReadStorage<'a, item::Potion>,
+        ReadStorage<'a, item::GoldPile>,
+        ReadStorage<'a, item::Key>,
         WriteStorage<'a, item::InBackpack>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut deck, mut log, names, mut positions, mut intent_pickup, potions, mut backpack) = data;
+        let (entities, mut deck, mut log, mut gold, mut map, names, mut positions, mut intent_pickup, potions, gold_piles,
+            keys, mut backpack) = data;
 
         for intent in intent_pickup.join() {
             positions.remove(intent.item);
+            // Gain gold
+            if let Some(pile) = gold_piles.get(intent.item) {
+                gold.gain(pile.amount);
+                log.push(format!("You pick up {} gold.", pile.amount));
+                entities.delete(intent.item).expect("Unable to delete gold pile");
+            }
+            // Gain keys
+            else if let Some(_) = keys.get(intent.item) {
+                map.keys += 1;
+                log.push("You pick up a key.".to_string());
+                entities.delete(intent.item).expect("Unable to delete key");
+            }
             // Gain potions
-            if let Some(_) = potions.get(intent.item) {
+            else if let Some(_) = potions.get(intent.item) {
                 backpack.insert(intent.item, item::InBackpack{ owner: intent.collected_by }).expect("Unable to pickup item");
                 log.push(format!("You pick up the {}.", names.get(intent.item).unwrap().name));
             }
@@ -1,15 +1,24 @@
 pub mod action;
+mod combat;
 mod damage;
 mod end_turn;
 mod inventory;
 mod map_index;
 mod monster;
+mod noise;
+mod orbs;
+mod terrain;
+pub mod trap;
 mod visibility;
 
+pub use combat::CombatSystem;
 pub use damage::DamageSystem;
 pub use damage::DeadCleanupSystem;
 pub use end_turn::EndTurnSystem;
 pub use inventory::InventorySystem;
 pub use map_index::MapIndexSystem;
 pub use monster::MonsterSystem;
+pub use noise::NoiseSystem;
+pub use orbs::OrbSystem;
+pub use terrain::TerrainSystem;
 pub use visibility::VisibilitySystem;
\ No newline at end of file
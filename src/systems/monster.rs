@@ -1,10 +1,40 @@
 use specs::prelude::*;
-use super::super::{Name, Gamelog, Map, Position, creature, item, RunState, status};
+use std::collections::HashMap;
+use super::super::{Name, Gamelog, Map, Position, flowfield::FlowField, map::{in_range, TileType}, creature, item, monsters, passive, RunState, spawner::SplitQueue, status};
 
 use rltk::{Point, RandomNumberGenerator};
 
 pub struct MonsterSystem {}
 
+// Turns a fleeing monster must spend out of the player's sight before it escapes
+const ESCAPE_TURNS: i32 = 3;
+
+// Moves onto the tile, or opens the door in the way instead. Returns true if a door was opened.
+fn step_to(map: &mut Map, pos: &mut Position, viewshed: &mut creature::Viewshed, idx: usize) -> bool {
+    if map.tiles[idx] == TileType::ClosedDoor {
+        map.open_door(idx);
+        return true;
+    }
+
+    let old_idx = map.xy_idx(pos.x, pos.y);
+    map.blocked[old_idx] = false;
+    pos.x = idx as i32 % map.width;
+    pos.y = idx as i32 / map.width;
+    map.blocked[idx] = true;
+    viewshed.dirty = true;
+    false
+}
+
+// Steps down the flee map away from the player, returning false when cornered
+fn step_away(map: &mut Map, pos: &mut Position, viewshed: &mut creature::Viewshed, flow: &FlowField) -> bool {
+    let idx = map.xy_idx(pos.x, pos.y);
+    if let Some(flee_target) = flow.step_away(map, idx) {
+        step_to(map, pos, viewshed, flee_target);
+        return true;
+    }
+    false
+}
+
 impl<'a> System<'a> for MonsterSystem {
     type SystemData = (
         Entities<'a>,
@@ -13,22 +43,31 @@ impl<'a> System<'a> for MonsterSystem {
         WriteExpect<'a, Gamelog>,
         WriteExpect<'a, Map>,
         WriteExpect<'a, RandomNumberGenerator>,
+        WriteExpect<'a, SplitQueue>,
         ReadStorage<'a, Name>,
         WriteStorage<'a, Position>,
-        ReadStorage<'a, item::Targeted>,
+        (ReadStorage<'a, item::Targeted>, ReadStorage<'a, item::TargetsAllies>, ReadStorage<'a, item::AreaOfEffect>),
         WriteStorage<'a, creature::Viewshed>,
         ReadStorage<'a, creature::Monster>,
         WriteStorage<'a, creature::CombatStats>,
         WriteStorage<'a, creature::AttackCycle>,
         WriteStorage<'a, creature::Intent>,
         WriteStorage<'a, creature::PerformAction>,
-        WriteStorage<'a, status::Poison>
+        WriteStorage<'a, creature::Asleep>,
+        (ReadStorage<'a, creature::Stationary>, ReadStorage<'a, creature::Splits>, ReadStorage<'a, creature::Gremlin>),
+        WriteStorage<'a, creature::ModeShift>,
+        (WriteStorage<'a, creature::Fleeing>, ReadStorage<'a, creature::Behaviour>, WriteStorage<'a, creature::EntityMoved>),
+        ReadStorage<'a, creature::Thief>,
+        (ReadExpect<'a, Entity>, WriteStorage<'a, creature::Awareness>, ReadStorage<'a, creature::Stealth>),
+        ReadStorage<'a, passive::Passives>,
+        (WriteStorage<'a, status::Poison>, ReadStorage<'a, status::Entangled>, WriteStorage<'a, status::Slowed>)
     );
 
     fn run(&mut self, data : Self::SystemData) {
-        let (entities, player_pos, runstate, mut log, mut map, mut rng, names, mut positions, targeted,
-            mut viewshed, monster, mut combat_stats, mut attack_cycles, mut monster_intents,
-            mut intent_action, mut status_poison) = data;
+        let (entities, player_pos, runstate, mut log, mut map, mut rng, mut split_queue, names, mut positions,
+            (targeted, targets_allies, aoe), mut viewshed, monster, mut combat_stats, mut attack_cycles, mut monster_intents,
+            mut intent_action, mut asleep, (stationary, splits, gremlins), mut mode_shifts, (mut fleeing, behaviours, mut entity_moved), thieves,
+            (player_entity, mut awareness, stealth), passives, (mut status_poison, status_entangled, mut status_slowed)) = data;
         
         // Skip if not on monsterturn
         if *runstate != RunState::MonsterTurn { return; }
@@ -54,15 +93,179 @@ impl<'a> System<'a> for MonsterSystem {
             }
         }
 
+        // Trigger passives that take effect at the start of the turn
+        for (ent, creature_passives, stats, _) in (&entities, &passives, &mut combat_stats, &monster).join() {
+            for p in creature_passives.triggered(passive::Trigger::TurnStart) {
+                if let passive::Passive::Ritual{amount} = p {
+                    stats.strength += amount;
+                    if let Some(ent_name) = names.get(ent) {
+                        log.push(format!("{} gains {} strength from Ritual.", ent_name.name, amount));
+                    }
+                }
+            }
+        }
+
+        // Snapshot every monster so support actions can pick out allies
+        let allies: Vec<(Entity, Point, i32)> = (&entities, &positions, &combat_stats, &monster).join()
+            .map(|(ent, pos, stats, _)| (ent, Point::new(pos.x, pos.y), stats.hp))
+            .collect();
+
+        // Shared distances to the player, so monsters do not each search for a path
+        let flow = FlowField::new(&map, *player_pos);
+
+        // Searching monsters share a field per spot they are heading for, built when first needed
+        let mut search_flows: HashMap<usize, FlowField> = HashMap::new();
+
+        let mut woken: Vec<Entity> = Vec::new();
+        let mut escaped: Vec<Entity> = Vec::new();
+        let mut opened_door = false;
         for (ent, mut viewshed, mut pos, ac, mut intent, _) in (&entities, &mut viewshed, &mut positions, &mut attack_cycles, &mut monster_intents, &monster).join() {
-            let distance = rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos);
+            // Sleeping monsters wake once disturbed, which takes up their turn
+            if let Some(sleep) = asleep.get_mut(ent) {
+                if sleep.turns < 1 {
+                    woken.push(ent);
+                    intent.used = true;
+                    ac.next_attack(&mut rng);
+                    if let Some(aware) = awareness.get_mut(ent) {
+                        aware.notice(*player_pos);
+                    }
+                    if let Some(ent_name) = names.get(ent) {
+                        log.push(format!("{} wakes up!", ent_name.name));
+                    }
+                } else if viewshed.visible_tiles.contains(&*player_pos) {
+                    sleep.turns -= 1;
+                }
+                continue;
+            }
+
+            // Fleeing monsters run from the player, and those that can escape leave the floor once out of sight
+            if let Some(flee) = fleeing.get_mut(ent) {
+                let idx = map.xy_idx(pos.x, pos.y);
+                if map.visible_tiles[idx] {
+                    flee.unseen_turns = 0;
+                } else {
+                    flee.unseen_turns += 1;
+                }
+
+                if flee.escapes && flee.unseen_turns >= ESCAPE_TURNS {
+                    escaped.push(ent);
+                } else if stationary.get(ent).is_none()
+                    && status::movement_blocked(status_entangled.get(ent), status_slowed.get_mut(ent)).is_none() {
+                    step_away(&mut map, &mut pos, &mut viewshed, &flow);
+                }
+                continue;
+            }
+
+            // Splitting takes up the monster's whole turn
+            if splits.get(ent).map_or(false, |split| split.ready) {
+                split_queue.splitting.push(ent);
+                continue;
+            }
+
+            // Movement goes through the map's blocked tiles, so monsters path around each other
+            let behaviour = behaviours.get(ent).copied().unwrap_or(creature::Behaviour::Rusher);
+            let can_move = stationary.get(ent).is_none()
+                && behaviour != creature::Behaviour::Guard{ provoked: false }
+                && status::movement_blocked(status_entangled.get(ent), status_slowed.get_mut(ent)).is_none();
+
+            // Monsters have to notice the player before they chase and attack
+            if let Some(aware) = awareness.get_mut(ent) {
+                let sees_player = viewshed.visible_tiles.contains(&*player_pos);
+                if aware.state == creature::Alertness::Hunting {
+                    if sees_player {
+                        aware.last_seen = Some(*player_pos);
+                    } else if let Some(last_seen) = aware.last_seen {
+                        aware.investigate(last_seen);
+                    }
+                } else if sees_player {
+                    let distance = rltk::DistanceAlg::Pythagoras.distance2d(Point::new(pos.x, pos.y), *player_pos);
+                    let lit = map.is_lit(player_pos.x, player_pos.y);
+                    let player_stealth = stealth.get(*player_entity).map_or(0, |s| s.amount());
+                    let chance = creature::detection_chance(distance, lit, player_stealth, aware.state == creature::Alertness::Searching);
+                    if rng.roll_dice(1, 100) <= chance {
+                        // Noticing the player takes up the monster's turn
+                        aware.notice(*player_pos);
+                        if let Some(ent_name) = names.get(ent) {
+                            log.push(format!("{} notices you!", ent_name.name));
+                        }
+                        continue;
+                    }
+                }
+
+                // Searching monsters head for where the player was last seen, giving up after a while
+                if aware.state == creature::Alertness::Searching {
+                    aware.search_turns -= 1;
+                    let arrived = aware.last_seen.map_or(true, |p| p.x == pos.x && p.y == pos.y);
+                    if arrived || aware.search_turns < 1 {
+                        aware.state = creature::Alertness::Unaware;
+                        aware.last_seen = None;
+                        if let Some(ent_name) = names.get(ent) {
+                            log.push(format!("{} loses track of you.", ent_name.name));
+                        }
+                    } else if can_move {
+                        let last_seen = aware.last_seen.unwrap();
+                        let search_flow = search_flows.entry(map.xy_idx(last_seen.x, last_seen.y))
+                            .or_insert_with(|| FlowField::toward(&map, last_seen));
+                        let idx = map.xy_idx(pos.x, pos.y);
+                        if let Some(next) = search_flow.step_toward(&map, idx) {
+                            opened_door |= step_to(&mut map, &mut pos, &mut viewshed, next);
+                        }
+                    }
+                }
+
+                if aware.state != creature::Alertness::Hunting { continue; }
+            }
+
+            // Skittish monsters panic once badly hurt, which takes up their turn, and keep
+            // retreating rather than leaving the floor
+            if let creature::Behaviour::Skittish{ flee_percent } = behaviour {
+                if let Some(stats) = combat_stats.get(ent) {
+                    if stats.hp * 100 < stats.max_hp * flee_percent {
+                        fleeing.insert(ent, creature::Fleeing{ unseen_turns: 0, escapes: false })
+                            .expect("Unable to insert creature::Fleeing for skittish monster");
+                        if let Some(ent_name) = names.get(ent) {
+                            log.push(format!("{} panics and flees!", ent_name.name));
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            // Support actions aim at allies in sight instead of the player, once the player is spotted
+            if targets_allies.get(intent.intent).is_some() && viewshed.visible_tiles.contains(&*player_pos) {
+                let origin = Point::new(pos.x, pos.y);
+                let range = targeted.get(intent.intent).map_or(1, |r| r.range);
+                let supported = monsters::supported_allies(ent, &allies, &gremlins);
+                let target = match aoe.get(intent.intent) {
+                    Some(_) => monsters::allies_in_range(ent, origin, range, &viewshed.visible_tiles, &supported)
+                        .first().map(|_| origin),
+                    None => monsters::weakest_ally_in_range(ent, origin, range, &viewshed.visible_tiles, &supported)
+                        .map(|(_, ally_pos, _)| ally_pos),
+                };
+                if target.is_some() {
+                    intent_action.insert(ent, creature::PerformAction{ action: intent.intent, target })
+                        .expect("Unable to insert intent::PerformAction for monsters");
+                }
+
+                // Monsters with no ally to support move on to their next attack
+                intent.used = true;
+                ac.next_attack(&mut rng);
+                continue;
+            }
+
+            let origin = Point::new(pos.x, pos.y);
             let range = match targeted.get(intent.intent) {
                 Some(r) => { r.range }
                 None => { 1 }
             };
-            let adjusted_range = range as f32 + { if range <= 1 { 0.5 } else { 0.0 } };
+            // Ranged intents need a line of fire that neither walls nor other creatures block
+            let can_reach = in_range(origin, *player_pos, range)
+                && (range <= 1 || map.has_line_of_fire(origin, *player_pos, true));
 
-            if distance < adjusted_range as f32 {
+            if behaviour == creature::Behaviour::Kiter && range > 1 && in_range(origin, *player_pos, 1) && can_move
+                && step_away(&mut map, &mut pos, &mut viewshed, &flow) {
+                // Kiters back off to get the player at their preferred range, attacking only when cornered
+            } else if can_reach {
                 // Perform action if player is in range
                 intent_action.insert(ent, creature::PerformAction{ action: intent.intent, target: Some(Point::new(player_pos.x, player_pos.y)) })
                     .expect("Unable to insert intent::PerformAction for monsters");
@@ -70,23 +273,63 @@ impl<'a> System<'a> for MonsterSystem {
                 // Pick next attack cycle
                 intent.used = true;
                 ac.next_attack(&mut rng);
-            } else if viewshed.visible_tiles.contains(&*player_pos) {
+
+                // Return to offense once the defensive cycle has played out
+                if let Some(mode) = mode_shifts.get_mut(ent) {
+                    if mode.defending && ac.cycle == 0 {
+                        mode.defending = false;
+                        mode.threshold += 10;
+                        ac.switch_sequential(mode.offensive.clone());
+                        if let Some(ent_name) = names.get(ent) {
+                            log.push(format!("{} shifts back into Offensive Mode.", ent_name.name));
+                        }
+                    }
+                }
+            } else if viewshed.visible_tiles.contains(&*player_pos) && can_move {
                 // Move towards the player
-                let path = rltk::a_star_search(
-                    map.xy_idx(pos.x, pos.y) as i32,
-                    map.xy_idx(player_pos.x, player_pos.y) as i32,
-                    &mut *map
-                );
-                if path.success && path.steps.len() > 1 {
-                    let mut idx = map.xy_idx(pos.x, pos.y);
-                    map.blocked[idx] = false;
-                    pos.x = path.steps[1] as i32 % map.width;
-                    pos.y = path.steps[1] as i32 / map.width;
-                    idx = map.xy_idx(pos.x, pos.y);
-                    map.blocked[idx] = true;
-                    viewshed.dirty = true;
+                let idx = map.xy_idx(pos.x, pos.y);
+                if let Some(next) = flow.step_toward(&map, idx) {
+                    opened_door |= step_to(&mut map, &mut pos, &mut viewshed, next);
+                }
+            }
+        }
+
+        for ent in woken {
+            asleep.remove(ent);
+        }
+
+        // Opened doors change what everyone can see
+        if opened_door {
+            for vs in (&mut viewshed).join() {
+                vs.dirty = true;
+            }
+        }
+
+        // Flag monsters that changed tiles so the terrain they entered can affect them
+        for (ent, start, _) in allies.iter() {
+            if let Some(pos) = positions.get(*ent) {
+                if pos.x != start.x || pos.y != start.y {
+                    entity_moved.insert(*ent, creature::EntityMoved{}).expect("Unable to insert creature::EntityMoved");
                 }
             }
         }
+
+        // Escaped monsters leave the floor, taking any stolen gold with them
+        for ent in escaped {
+            if let Some(pos) = positions.get(ent) {
+                let idx = map.xy_idx(pos.x, pos.y);
+                map.blocked[idx] = false;
+            }
+            if let Some(ent_name) = names.get(ent) {
+                match thieves.get(ent) {
+                    Some(thief) if thief.stolen > 0 => log.push(format!("{} escapes with {} gold!", ent_name.name, thief.stolen)),
+                    _ => log.push(format!("{} escapes!", ent_name.name)),
+                }
+            }
+            if let Some(intent) = monster_intents.get(ent) {
+                entities.delete(intent.intent).expect("Unable to delete escaped monster's intent");
+            }
+            entities.delete(ent).expect("Unable to delete escaped monster");
+        }
     }
 }
\ No newline at end of file
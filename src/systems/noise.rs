@@ -0,0 +1,44 @@
+use specs::prelude::*;
+use super::super::{Name, Gamelog, Map, Position, creature, noise::{NoiseEvents, NoiseField}, RunState};
+
+pub struct NoiseSystem {}
+
+impl<'a> System<'a> for NoiseSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, RunState>,
+        ReadExpect<'a, Map>,
+        WriteExpect<'a, Gamelog>,
+        WriteExpect<'a, NoiseEvents>,
+        WriteExpect<'a, NoiseField>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, creature::Monster>,
+        ReadStorage<'a, creature::Asleep>,
+        WriteStorage<'a, creature::Awareness>,
+    );
+
+    fn run(&mut self, data : Self::SystemData) {
+        let (entities, runstate, map, mut log, mut events, mut field, names, positions, monsters, asleep, mut awareness) = data;
+
+        // Noises made since the monsters last acted are heard right before they act
+        if *runstate != RunState::MonsterTurn { return; }
+
+        field.clear((map.width * map.height) as usize);
+        for noise in events.noises.drain(..) {
+            field.add(&map, &noise);
+        }
+
+        // Monsters that hear a noise go to see what made it, unless they are already hunting
+        for (ent, pos, aware, _, _) in (&entities, &positions, &mut awareness, &monsters, !&asleep).join() {
+            if aware.state == creature::Alertness::Hunting { continue; }
+            let idx = map.xy_idx(pos.x, pos.y);
+            if let Some(source) = field.source[idx] {
+                aware.investigate(source);
+                if let (true, Some(ent_name)) = (map.visible_tiles[idx], names.get(ent)) {
+                    log.push(format!("{} turns toward a noise.", ent_name.name));
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
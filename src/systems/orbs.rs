@@ -0,0 +1,100 @@
+use specs::prelude::*;
+use super::super::{
+    Name, Position, Gamelog, RunState,
+    creature, orb,
+};
+
+use rltk::{Point, RandomNumberGenerator};
+
+pub struct OrbSystem {}
+
+impl<'a> System<'a> for OrbSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, RunState>,
+        WriteExpect<'a, Gamelog>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, creature::Monster>,
+        ReadStorage<'a, creature::Viewshed>,
+        WriteStorage<'a, creature::Player>,
+        WriteStorage<'a, creature::CombatStats>,
+        WriteStorage<'a, creature::SufferDamage>,
+        WriteStorage<'a, orb::OrbSlots>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, player_entity, runstate, mut log, mut rng, names, positions, monsters, viewsheds,
+            mut players, mut combat_stats, mut suffer_damage, mut orb_slots) = data;
+
+        let slots = match orb_slots.get_mut(*player_entity) {
+            Some(slots) => slots,
+            None => return,
+        };
+
+        // Orbs evoked this turn
+        let mut triggered: Vec<(orb::Orb, bool)> = slots.evoked.drain(..).map(|o| (o, true)).collect();
+
+        // Passive orb effects at the end of the player's turn
+        if let RunState::EndTurn{ player_end_turn: true } = *runstate {
+            for o in slots.orbs.iter_mut() {
+                if let orb::Orb::Dark{ damage } = o {
+                    *damage += 6;
+                }
+                triggered.push((*o, false));
+            }
+        }
+        if triggered.is_empty() { return; }
+
+        // Orbs can only strike monsters within the player's viewshed
+        let mut targets: Vec<Entity> = Vec::new();
+        if let Some(viewshed) = viewsheds.get(*player_entity) {
+            for (ent, pos, _, _) in (&entities, &positions, &monsters, &combat_stats).join() {
+                if viewshed.visible_tiles.contains(&Point::new(pos.x, pos.y)) {
+                    targets.push(ent);
+                }
+            }
+        }
+
+        for (o, evoke) in triggered {
+            let damage = o.damage(evoke);
+            if damage > 0 {
+                // Dark orbs strike the weakest monster, others strike a random one
+                let target = match o {
+                    orb::Orb::Dark{..} => {
+                        targets.iter()
+                            .min_by_key(|t| combat_stats.get(**t).map_or(i32::MAX, |s| s.hp))
+                            .copied()
+                    }
+                    _ => rng.random_slice_entry(&targets).copied()
+                };
+                if let Some(target) = target {
+                    creature::SufferDamage::new_damage(&mut suffer_damage, target, damage);
+                    log.push(format!("{} orb strikes {} for {} damage.",
+                        o.to_name(),
+                        names.get(target).unwrap().name,
+                        damage));
+                }
+            }
+
+            let block = o.block(evoke);
+            if block > 0 {
+                if let Some(stats) = combat_stats.get_mut(*player_entity) {
+                    stats.block += block;
+                    log.push(format!("{} orb grants {} block.", o.to_name(), block));
+                }
+            }
+
+            // Passive energy is granted when energy is restored at the start of the next turn
+            let energy = if evoke { o.energy(true) } else { 0 };
+            if energy > 0 {
+                if let Some(player) = players.get_mut(*player_entity) {
+                    player.energy += energy;
+                    log.push(format!("{} orb grants {} energy.", o.to_name(), energy));
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+use specs::prelude::*;
+use super::super::{Name, Gamelog, Map, Position, RunState, creature, map::{TileType, TileEffectType}, status};
+
+pub struct TerrainSystem {}
+
+const CALTROPS_DAMAGE: i32 = 3;
+const GAS_POISON: i32 = 2;
+// Slipping on oil holds a creature in place through its next turn
+const SLIP_TURNS: i32 = 2;
+// Wading through water or climbing over rubble costs a creature its next move
+const WADE_TURNS: i32 = 2;
+const LAVA_DAMAGE: i32 = 5;
+
+impl<'a> System<'a> for TerrainSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, RunState>,
+        WriteExpect<'a, Map>,
+        WriteExpect<'a, Gamelog>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, creature::Creature>,
+        WriteStorage<'a, creature::Viewshed>,
+        WriteStorage<'a, creature::EntityMoved>,
+        WriteStorage<'a, creature::SufferDamage>,
+        WriteStorage<'a, status::Poison>,
+        WriteStorage<'a, status::Entangled>,
+        WriteStorage<'a, status::Slowed>,
+    );
+
+    fn run(&mut self, data : Self::SystemData) {
+        let (entities, runstate, mut map, mut log, names, positions, creatures, mut viewsheds,
+            mut entity_moved, mut suffer_damage, mut status_poison, mut status_entangled, mut status_slowed) = data;
+
+        // Creatures are affected by the terrain they step onto
+        for (ent, pos, _) in (&entities, &positions, &entity_moved).join() {
+            let idx = map.xy_idx(pos.x, pos.y);
+            let ent_name = names.get(ent).map_or("Something".to_string(), |n| n.name.to_string());
+            match map.tiles[idx] {
+                TileType::ShallowWater | TileType::Rubble => {
+                    if status_slowed.get(ent).is_none() {
+                        status_slowed.insert(ent, status::Slowed{ turns: WADE_TURNS, started: false })
+                            .expect("Unable to insert status::Slowed");
+                    }
+                }
+                TileType::Lava => {
+                    creature::SufferDamage::new_damage(&mut suffer_damage, ent, LAVA_DAMAGE);
+                    log.push(format!("{} is burned by the embers for {} damage.", ent_name, LAVA_DAMAGE));
+                }
+                _ => {}
+            }
+
+            let effect = match map.tile_effects.get(&idx) {
+                Some(effect) => effect.effect,
+                None => continue,
+            };
+            match effect {
+                TileEffectType::Caltrops => {
+                    creature::SufferDamage::new_damage(&mut suffer_damage, ent, CALTROPS_DAMAGE);
+                    log.push(format!("{} steps on caltrops for {} damage.", ent_name, CALTROPS_DAMAGE));
+                }
+                TileEffectType::Oil => {
+                    if status_entangled.get(ent).is_none() {
+                        status_entangled.insert(ent, status::Entangled{ turns: SLIP_TURNS })
+                            .expect("Unable to insert status::Entangled");
+                        log.push(format!("{} slips on the oil.", ent_name));
+                    }
+                }
+                _ => {}
+            }
+        }
+        entity_moved.clear();
+
+        // Lingering effects act and decay once per round, as the monsters take their turn
+        if *runstate != RunState::MonsterTurn { return; }
+
+        for (ent, pos, _) in (&entities, &positions, &creatures).join() {
+            let idx = map.xy_idx(pos.x, pos.y);
+            if map.tile_effects.get(&idx).map_or(false, |e| e.effect == TileEffectType::PoisonGas) {
+                if let Some(poison) = status_poison.get_mut(ent) {
+                    poison.turns += GAS_POISON;
+                } else {
+                    status_poison.insert(ent, status::Poison{ turns: GAS_POISON })
+                        .expect("Unable to insert status::Poison");
+                }
+                if let Some(ent_name) = names.get(ent) {
+                    log.push(format!("{} breathes in poison gas.", ent_name.name));
+                }
+            }
+        }
+
+        let mut expired_smoke = false;
+        map.tile_effects.retain(|_, effect| {
+            effect.turns -= 1;
+            if effect.turns < 1 && effect.effect == TileEffectType::Smoke {
+                expired_smoke = true;
+            }
+            effect.turns > 0
+        });
+
+        // Clearing smoke changes what everyone can see
+        if expired_smoke {
+            for viewshed in (&mut viewsheds).join() {
+                viewshed.dirty = true;
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,103 @@
+use specs::prelude::*;
+use rltk::{Point, RandomNumberGenerator};
+use super::super::{Name, Gamelog, Map, Position, cards, creature, deck::Deck, map::TrapType, noise, status};
+use super::action::relocate;
+
+const SPIKE_DAMAGE: i32 = 6;
+const ALARM_NOISE: i32 = 14;
+
+/// Sets off the traps that creatures stepped onto this turn. Traps go off for monsters too,
+/// though they path around the traps that have been revealed.
+pub fn run(ecs: &mut World) {
+    let triggered: Vec<(Entity, usize, TrapType)> = {
+        let entities = ecs.entities();
+        let positions = ecs.read_storage::<Position>();
+        let entity_moved = ecs.read_storage::<creature::EntityMoved>();
+        let map = ecs.fetch::<Map>();
+        (&entities, &positions, &entity_moved).join()
+            .filter_map(|(ent, pos, _)| {
+                let idx = map.xy_idx(pos.x, pos.y);
+                map.traps.get(&idx).map(|t| (ent, idx, t.trap))
+            })
+            .collect()
+    };
+
+    let player_entity = *ecs.fetch::<Entity>();
+    for (ent, idx, trap) in triggered {
+        let ent_name = ecs.read_storage::<Name>().get(ent).map_or("Something".to_string(), |n| n.name.to_string());
+        let (x, y, seen) = {
+            // Traps the player sees go off stay revealed
+            let mut map = ecs.fetch_mut::<Map>();
+            let seen = ent == player_entity || map.visible_tiles[idx];
+            if seen {
+                if let Some(t) = map.traps.get_mut(&idx) { t.revealed = true; }
+            }
+            (idx as i32 % map.width, idx as i32 / map.width, seen)
+        };
+
+        match trap {
+            TrapType::Spike => {
+                creature::SufferDamage::new_damage(&mut ecs.write_storage::<creature::SufferDamage>(), ent, SPIKE_DAMAGE);
+                if seen {
+                    ecs.fetch_mut::<Gamelog>().push(format!("{} steps on a spike trap for {} damage.", ent_name, SPIKE_DAMAGE));
+                }
+            }
+            TrapType::Alarm => {
+                ecs.fetch_mut::<noise::NoiseEvents>().emit(x, y, ALARM_NOISE);
+                ecs.fetch_mut::<Gamelog>().push("An alarm rings out!".to_string());
+
+                // Unlike other noises, an alarm is loud enough to wake sleeping monsters that hear it
+                let map = ecs.fetch::<Map>();
+                let mut heard = vec![false; map.tiles.len()];
+                for (idx, _) in noise::propagate(&map, x, y, ALARM_NOISE) {
+                    heard[idx] = true;
+                }
+                let positions = ecs.read_storage::<Position>();
+                let mut asleep = ecs.write_storage::<creature::Asleep>();
+                for (pos, sleep) in (&positions, &mut asleep).join() {
+                    if heard[map.xy_idx(pos.x, pos.y)] {
+                        sleep.turns = 0;
+                    }
+                }
+            }
+            TrapType::Teleport => {
+                if ecs.read_storage::<status::Rooted>().get(ent).is_some() {
+                    if seen {
+                        ecs.fetch_mut::<Gamelog>().push(format!("{} is Rooted and resists the teleport trap.", ent_name));
+                    }
+                } else if let Some(dest) = teleport_destination(ecs) {
+                    let mut map = ecs.fetch_mut::<Map>();
+                    relocate(ecs, &mut map, ent, dest);
+                    if seen {
+                        ecs.fetch_mut::<Gamelog>().push(format!("{} is whisked away by a teleport trap!", ent_name));
+                    }
+                }
+            }
+            TrapType::Curse => {
+                // Curses only take hold of the player's deck
+                if ent == player_entity {
+                    let curse = cards::neutral::injury(ecs);
+                    ecs.fetch_mut::<Deck>().shuffle_in(curse);
+                    ecs.fetch_mut::<Gamelog>().push("A curse trap shuffles an Injury into your deck.".to_string());
+                }
+            }
+        }
+    }
+}
+
+// A random open tile in one of the floor's rooms, avoiding hazards and other traps
+fn teleport_destination(ecs: &World) -> Option<Point> {
+    let map = ecs.fetch::<Map>();
+    let mut rng = ecs.write_resource::<RandomNumberGenerator>();
+    for _ in 0 .. 100 {
+        let room = map.rooms[rng.range(0, map.rooms.len() as i32) as usize];
+        let x = rng.range(room.x1 + 1, room.x2 + 1);
+        let y = rng.range(room.y1 + 1, room.y2 + 1);
+        let idx = map.xy_idx(x, y);
+        if map.tiles[idx].is_walkable() && map.tiles[idx].movement_cost() <= 1.0
+            && map.tile_content[idx].is_empty() && !map.traps.contains_key(&idx) {
+            return Some(Point::new(x, y));
+        }
+    }
+    None
+}